@@ -27,5 +27,101 @@ pub enum Command {
     GenerateSchema {
         #[clap(long = "override")]
         override_schema: bool,
+
+        #[clap(
+            help = "Compare the schema with the database without writing it",
+            long = "check"
+        )]
+        check: bool,
+    },
+
+    #[clap(name = "migrate")]
+    Migrate {
+        #[clap(subcommand)]
+        action: MigrateAction,
+    },
+
+    #[clap(name = "config")]
+    Config {
+        #[clap(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    #[clap(name = "schema", about = "Print the JSON Schema of the config file")]
+    Schema,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateAction {
+    #[clap(
+        name = "diff",
+        about = "Write the migration turning the database into the schema"
+    )]
+    Diff {
+        #[clap(
+            help = "Name of the migration",
+            long = "name",
+            default_value = "migration"
+        )]
+        name: String,
+
+        #[clap(
+            help = "Directory of the migration files, relative to the config file",
+            long = "dir",
+            default_value = "migrations"
+        )]
+        migrations_dir: String,
+    },
+
+    #[clap(name = "up", about = "Apply the pending migrations")]
+    Up {
+        #[clap(
+            help = "Directory of the migration files, relative to the config file",
+            long = "dir",
+            default_value = "migrations"
+        )]
+        migrations_dir: String,
+
+        #[clap(
+            help = "Regenerate the schema file after migrating",
+            long = "regenerate-schema"
+        )]
+        regenerate_schema: bool,
+    },
+
+    #[clap(name = "down", about = "Revert the last applied migrations")]
+    Down {
+        #[clap(
+            help = "Directory of the migration files, relative to the config file",
+            long = "dir",
+            default_value = "migrations"
+        )]
+        migrations_dir: String,
+
+        #[clap(
+            help = "Number of migrations to revert",
+            long = "steps",
+            default_value = "1"
+        )]
+        steps: usize,
+
+        #[clap(
+            help = "Regenerate the schema file after migrating",
+            long = "regenerate-schema"
+        )]
+        regenerate_schema: bool,
+    },
+
+    #[clap(name = "status", about = "List the applied and pending migrations")]
+    Status {
+        #[clap(
+            help = "Directory of the migration files, relative to the config file",
+            long = "dir",
+            default_value = "migrations"
+        )]
+        migrations_dir: String,
     },
 }
@@ -1,119 +1,239 @@
 use crate::cli;
 use crate::config;
 use crate::data;
+use crate::dialects::{self, Connection, Dialect};
 use crate::error;
 use crate::generate_schema_command::GenerateSchemaCommand;
+use crate::migrate_command::MigrateCommand;
 use crate::plugins::PluginResult;
 use crate::plugins::TypeScriptPgPlugin;
 use crate::plugins::{
     Plugin, TypeScriptGenericSdkPlugin, TypeScriptOperationsPlugin, TypeScriptPlugin,
 };
-use glob::glob;
-use postgres::NoTls;
+use glob::{glob, Pattern};
 use std::vec;
-use std::{env, fs, path::PathBuf};
+use std::{fs, path::PathBuf};
 
 #[derive()]
 pub struct Codegen {
     pub cli: cli::Cli,
     pub config: config::Config,
+    pub dialect: Box<dyn Dialect>,
 }
 
 impl Codegen {
-    pub fn new() -> Result<Codegen, error::CodegenError> {
-        // Collect the CLI arguments.
-        let cli = cli::Cli::new();
-
+    pub fn new(cli: cli::Cli) -> Result<Codegen, error::CodegenError> {
         // Create config struct from the CLI config argument.
-        let config = config::Config::new(&cli.config_file_path)?;
-        println!("{:#?}", config);
+        let mut config = config::Config::new(&cli.config_file_path)?;
+        let dialect = dialects::from_name(&config.dialect)?;
+        if let Some(connection) = &mut config.connection {
+            if dialect.has_database_file() && connection.database != ":memory:" {
+                connection.database = config
+                    .config_dir
+                    .join(&connection.database)
+                    .to_string_lossy()
+                    .to_string();
+            }
+            for path in [
+                &mut connection.ssl_root_cert,
+                &mut connection.ssl_cert,
+                &mut connection.ssl_key,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *path = config.config_dir.join(&*path).to_string_lossy().to_string();
+            }
+        }
 
-        Ok(Codegen { cli, config })
+        Ok(Codegen {
+            cli,
+            config,
+            dialect,
+        })
     }
 
-    pub fn connect(&self) -> Result<postgres::Client, error::CodegenError> {
-        let host = &self.config.connection.host;
-        let user = &self.config.connection.user;
-        let port = &self.config.connection.port;
-        let database = &self.config.connection.database;
-        let password = &self.config.connection.password;
-        let password = match password {
-            Some(password) => format!(" password={password}"),
-            None => "".to_string(),
-        };
-        let params = format!("host={host} user={user} port={port} dbname={database}{password}");
-        let client = postgres::Client::connect(&params, NoTls)?;
-        Ok(client)
+    pub fn get_connection_config(&self) -> Result<&config::ConnectionConfig, error::CodegenError> {
+        self.config.connection.as_ref().ok_or_else(|| {
+            error::CodegenError::ConfigError(
+                "The \"connection\" config property is required to connect to the database"
+                    .to_string(),
+            )
+        })
+    }
+
+    pub fn connect(&self) -> Result<Box<dyn Connection>, error::CodegenError> {
+        self.dialect.connect(self.get_connection_config()?)
+    }
+
+    // The relative paths of the config are relative to the config file.
+    pub fn resolve_path(&self, path: &str) -> PathBuf {
+        self.config.config_dir.join(path)
     }
 
     pub fn get_schema_file_path(&self) -> Result<PathBuf, error::CodegenError> {
-        let current_dir = env::current_dir()?;
-        Ok(current_dir.join(&self.config.schema))
+        match &self.config.schema {
+            config::SchemaConfig::File(schema) => Ok(self.resolve_path(schema)),
+            config::SchemaConfig::Migrations { .. } => Err(error::CodegenError::ConfigError(
+                "The \"schema\" config property points to migrations instead of the schema file"
+                    .to_string(),
+            )),
+        }
     }
 
-    pub fn get_query_file_paths(&self) -> Result<Vec<PathBuf>, error::CodegenError> {
-        let glob_pattern = &self.config.queries;
-        let mut query_file_paths = vec![];
-        let glob_result = glob(glob_pattern);
-        let entries = match glob_result {
-            Ok(entries) => entries,
-            Err(_) => {
-                return Err(error::CodegenError::ConfigError(
-                    format!("Invalid glob pattern value ({glob_pattern}) in the \"queries\" config property")
-                ));
-            }
+    // The queries of the `generate` entry override the shared ones. The
+    // files matched by the excluding patterns, like `!queries/admin/**`, are
+    // left out.
+    pub fn get_query_file_paths(
+        &self,
+        generate_index: usize,
+    ) -> Result<Vec<PathBuf>, error::CodegenError> {
+        let (queries, property) = match &self.config.generate[generate_index].queries {
+            Some(queries) => (queries, format!("generate[{generate_index}].queries")),
+            None => (&self.config.queries, "queries".to_string()),
         };
+        let mut query_file_paths = vec![];
+        for glob_pattern in queries.get_include_patterns() {
+            query_file_paths.extend(self.get_sql_file_paths(glob_pattern, &property)?);
+        }
+        let exclude_patterns = queries
+            .get_exclude_patterns()
+            .iter()
+            .map(|glob_pattern| {
+                Pattern::new(&self.get_config_dir_pattern(glob_pattern))
+                    .map_err(|_| Codegen::get_invalid_glob_pattern_error(glob_pattern, &property))
+            })
+            .collect::<Result<Vec<Pattern>, error::CodegenError>>()?;
+        query_file_paths.retain(|query_file_path| {
+            !exclude_patterns
+                .iter()
+                .any(|exclude_pattern| exclude_pattern.matches_path(query_file_path))
+        });
+        query_file_paths.sort();
+        query_file_paths.dedup();
+        Ok(query_file_paths)
+    }
+
+    pub fn get_migration_file_paths(
+        &self,
+        glob_pattern: &str,
+    ) -> Result<Vec<PathBuf>, error::CodegenError> {
+        let mut migration_file_paths =
+            self.get_sql_file_paths(glob_pattern, "schema.migrations")?;
+        // Migrations are applied in the order of their file names.
+        migration_file_paths.sort();
+        Ok(migration_file_paths)
+    }
+
+    // The files without the `.sql` extension, like `README.md` matched by
+    // `queries/**/*`, are skipped.
+    fn get_sql_file_paths(
+        &self,
+        glob_pattern: &str,
+        property: &str,
+    ) -> Result<Vec<PathBuf>, error::CodegenError> {
+        let entries = glob(&self.get_config_dir_pattern(glob_pattern))
+            .map_err(|_| Codegen::get_invalid_glob_pattern_error(glob_pattern, property))?;
+        let mut sql_file_paths = vec![];
         for entry in entries {
-            match entry {
-                Ok(path) => query_file_paths.push(path),
-                Err(_) => {
-                    return Err(error::CodegenError::ConfigError(
-                        format!("Invalid glob pattern value ({glob_pattern}) in the \"queries\" config property")
-                    ));
-                }
+            let path = entry
+                .map_err(|_| Codegen::get_invalid_glob_pattern_error(glob_pattern, property))?;
+            let is_sql_file = path.is_file()
+                && path
+                    .extension()
+                    .map(|extension| extension == "sql")
+                    .unwrap_or_default();
+            if is_sql_file {
+                sql_file_paths.push(path);
             }
         }
+        Ok(sql_file_paths)
+    }
 
-        for query_file_path in &query_file_paths {
-            let path = query_file_path.to_str().unwrap();
-            match query_file_path.extension() {
-                Some(extension) => {
-                    if extension != "sql" {
-                        return Err(error::CodegenError::ConfigError(
-                            format!("Invalid glob pattern ({glob_pattern}) in the \"queries\" property that resolves to the \"{path}\" path that is not a valid SQL file"),
-                        ));
-                    }
-                }
-                None => {
-                    return Err(error::CodegenError::ConfigError(
-                        format!("Invalid glob pattern ({glob_pattern}) in the \"queries\" property that resolves to the \"{path}\" path that is not a valid SQL file"),
-                    ));
-                }
+    // The config directory is escaped, so it's matched literally.
+    fn get_config_dir_pattern(&self, glob_pattern: &str) -> String {
+        let config_dir = Pattern::escape(&self.config.config_dir.to_string_lossy());
+        PathBuf::from(config_dir)
+            .join(glob_pattern)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn get_invalid_glob_pattern_error(glob_pattern: &str, property: &str) -> error::CodegenError {
+        error::CodegenError::ConfigError(format!(
+            "Invalid glob pattern value ({glob_pattern}) in the \"{property}\" config property"
+        ))
+    }
+
+    pub fn get_database(&self) -> Result<data::Database, error::CodegenError> {
+        match &self.config.schema {
+            config::SchemaConfig::File(_) => data::Database::from_schema_file_path(
+                self.dialect.as_ref(),
+                self.get_schema_file_path()?,
+            ),
+            config::SchemaConfig::Migrations { migrations } => {
+                data::Database::from_migration_file_paths(
+                    self.dialect.as_ref(),
+                    self.get_migration_file_paths(migrations)?,
+                )
             }
         }
-        Ok(query_file_paths)
     }
 
     pub fn run(&self) -> Result<(), error::CodegenError> {
         // Run command if provided.
         if let Some(command) = &self.cli.command {
             match command {
-                cli::Command::GenerateSchema { override_schema } => {
-                    // Generate schema DDL if the file does not exist.
-                    GenerateSchemaCommand::run(self, *override_schema)?;
+                cli::Command::GenerateSchema {
+                    override_schema,
+                    check,
+                } => {
+                    if *check {
+                        GenerateSchemaCommand::check(self)?;
+                    } else {
+                        // Generate schema DDL if the file does not exist.
+                        GenerateSchemaCommand::run(self, *override_schema)?;
+                    }
                 }
+                cli::Command::Migrate { action } => match action {
+                    cli::MigrateAction::Diff {
+                        name,
+                        migrations_dir,
+                    } => {
+                        MigrateCommand::diff(self, name, migrations_dir)?;
+                    }
+                    cli::MigrateAction::Up {
+                        migrations_dir,
+                        regenerate_schema,
+                    } => {
+                        MigrateCommand::up(self, migrations_dir, *regenerate_schema)?;
+                    }
+                    cli::MigrateAction::Down {
+                        migrations_dir,
+                        steps,
+                        regenerate_schema,
+                    } => {
+                        MigrateCommand::down(self, migrations_dir, *steps, *regenerate_schema)?;
+                    }
+                    cli::MigrateAction::Status { migrations_dir } => {
+                        MigrateCommand::status(self, migrations_dir)?;
+                    }
+                },
+                // The config commands run before the config file is read.
+                cli::Command::Config { .. } => unreachable!(),
             }
         }
         // Generate all the files specified in the config.
         else {
-            // Generate data to be shared between plugins, to generate types.
-            let database = data::Database::from_schema_file_path(self.get_schema_file_path()?)?;
-            let queries =
-                data::Query::from_query_file_paths(&database, self.get_query_file_paths()?)?;
-            let data = data::Data::new(&database, &queries);
+            // The database is shared between the generated targets.
+            let database = self.get_database()?;
 
             // Initialize plugins.
-            let typescript_plugin = TypeScriptPlugin::new();
+            let typescript_plugin = TypeScriptPlugin::new(
+                self.dialect.as_ref(),
+                &self.config.scalars,
+                &self.config.overrides,
+            );
             let typescript_operation_plugin = TypeScriptOperationsPlugin::new(&typescript_plugin);
             let typescript_generic_sdk_plugin =
                 TypeScriptGenericSdkPlugin::new(&typescript_operation_plugin);
@@ -125,7 +245,14 @@ impl Codegen {
                 &typescript_pg_plugin,
             ];
 
-            for generate_config in self.config.generate.iter() {
+            for (generate_index, generate_config) in self.config.generate.iter().enumerate() {
+                let queries = data::Query::from_query_file_paths(
+                    self.dialect.as_ref(),
+                    &database,
+                    self.get_query_file_paths(generate_index)?,
+                )?;
+                // Generate data to be shared between plugins, to generate types.
+                let data = data::Data::new(&database, &queries);
                 let mut plugin_results: PluginResult = PluginResult::new();
                 for plugin_config in generate_config.plugins.iter() {
                     let plugin = plugins
@@ -137,9 +264,12 @@ impl Codegen {
                             plugin_config.name.clone()
                         )));
                     }
-                    plugin_results.append(&mut plugin.unwrap().run(&data));
+                    plugin_results.append(&mut plugin.unwrap().run(&data)?);
                 }
-                fs::write(&generate_config.output, plugin_results.to_string())?
+                fs::write(
+                    self.resolve_path(&generate_config.output),
+                    plugin_results.to_string(),
+                )?
             }
         }
         Ok(())
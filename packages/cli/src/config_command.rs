@@ -0,0 +1,19 @@
+use crate::{cli, config, error};
+
+pub struct ConfigCommand {}
+
+impl ConfigCommand {
+    pub fn run(action: &cli::ConfigAction) -> Result<(), error::CodegenError> {
+        match action {
+            cli::ConfigAction::Schema => ConfigCommand::schema(),
+        }
+    }
+
+    // Prints the JSON Schema of the config file, which editors use to
+    // validate and autocomplete it.
+    fn schema() -> Result<(), error::CodegenError> {
+        let schema = schemars::schema_for!(config::Config);
+        println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+        Ok(())
+    }
+}
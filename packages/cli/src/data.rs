@@ -1,15 +1,31 @@
 mod column;
+mod column_attributes;
 mod data;
 mod database;
+mod database_diff;
+mod enum_type;
+mod function;
+mod json_access;
+mod migration;
 mod projection;
 mod query;
+mod schema_change;
 mod selection;
 mod table;
+mod table_definition;
 
-pub use column::Column;
+pub use column::{Column, ColumnIdentity};
+pub use column_attributes::ColumnAttributes;
 pub use data::Data;
 pub use database::Database;
+pub use database_diff::DatabaseDiff;
+pub use enum_type::EnumType;
+pub use function::Function;
+pub use json_access::JsonAccess;
+pub use migration::Migration;
 pub use projection::Projection;
 pub use query::Query;
+pub use schema_change::{AlterTableAction, SchemaChange};
 pub use selection::Selection;
 pub use table::Table;
+pub use table_definition::TableDefinition;
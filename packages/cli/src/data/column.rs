@@ -1,5 +1,14 @@
+use super::ColumnAttributes;
+use crate::utils;
 use sqlparser::ast::{ColumnDef, ColumnOption, DataType};
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnIdentity {
+    Always,
+    ByDefault,
+    Serial,
+}
+
 #[derive(Debug)]
 pub struct Column {
     pub name: String,
@@ -8,6 +17,17 @@ pub struct Column {
     pub is_unique: bool,
     pub is_not_null: bool,
     pub default_value: Option<String>,
+    pub identity: Option<ColumnIdentity>,
+    pub generation_expression: Option<String>,
+    // The SQL type is the type of the array elements for the array columns.
+    pub array_dimensions: usize,
+    pub description: Option<String>,
+    pub alias: Option<String>,
+    // Literal values the column is limited to by the check constraint.
+    pub allowed_values: Option<Vec<String>>,
+    // Type from the `@type` annotation, like the TypeScript type or the JSON
+    // Schema of the JSON column.
+    pub type_annotation: Option<String>,
 }
 
 impl Clone for Column {
@@ -19,6 +39,13 @@ impl Clone for Column {
             is_unique: self.is_unique,
             is_not_null: self.is_not_null,
             default_value: self.default_value.clone(),
+            identity: self.identity,
+            generation_expression: self.generation_expression.clone(),
+            array_dimensions: self.array_dimensions,
+            description: self.description.clone(),
+            alias: self.alias.clone(),
+            allowed_values: self.allowed_values.clone(),
+            type_annotation: self.type_annotation.clone(),
         }
     }
 }
@@ -31,6 +58,13 @@ impl PartialEq for Column {
             && self.is_unique == other.is_unique
             && self.is_not_null == other.is_not_null
             && self.default_value == other.default_value
+            && self.identity == other.identity
+            && self.generation_expression == other.generation_expression
+            && self.array_dimensions == other.array_dimensions
+            && self.description == other.description
+            && self.alias == other.alias
+            && self.allowed_values == other.allowed_values
+            && self.type_annotation == other.type_annotation
     }
 }
 
@@ -50,10 +84,20 @@ impl Column {
             is_unique,
             is_not_null,
             default_value,
+            identity: None,
+            generation_expression: None,
+            array_dimensions: 0,
+            description: None,
+            alias: None,
+            allowed_values: None,
+            type_annotation: None,
         }
     }
 
-    pub fn from_column_definition(column_definition: &ColumnDef) -> Column {
+    pub fn from_column_definition(
+        column_definition: &ColumnDef,
+        attributes: &ColumnAttributes,
+    ) -> Column {
         let is_not_null = column_definition
             .options
             .iter()
@@ -72,21 +116,89 @@ impl Column {
                 ColumnOption::Unique { is_primary } => is_primary,
                 _ => false,
             });
-        let default_value =
+        let default_value = attributes.default_value.clone().or_else(|| {
             column_definition
                 .options
                 .iter()
-                .find_map(|column| match column.option {
-                    ColumnOption::Default { .. } => Some("DEFAULT".to_string()),
+                .find_map(|column| match &column.option {
+                    ColumnOption::Default(expr) => Some(expr.to_string()),
                     _ => None,
-                });
-        Column::new(
+                })
+        });
+        let mut column = Column::new(
             column_definition.name.value.clone(),
             column_definition.data_type.clone(),
             is_primary_key,
             is_unique,
-            is_not_null,
+            // Primary keys, identity and serial columns are implicitly not null.
+            is_not_null || is_primary_key || attributes.identity.is_some(),
             default_value,
-        )
+        );
+        column.identity = attributes.identity;
+        column.generation_expression = attributes.generation_expression.clone();
+        column.array_dimensions = attributes.array_dimensions;
+        column.alias = attributes.alias.clone();
+        column.allowed_values = attributes.allowed_values.clone();
+        column.type_annotation = attributes.type_annotation.clone();
+        column.description = attributes.description.clone();
+        column
+    }
+
+    pub fn get_type_ddl(&self) -> String {
+        format!("{}{}", self.sql_type, "[]".repeat(self.array_dimensions))
+    }
+
+    pub fn get_definition_ddl(&self) -> String {
+        let sql_type = match (self.identity, &self.sql_type) {
+            (Some(ColumnIdentity::Serial), DataType::SmallInt(_)) => "SMALLSERIAL".to_string(),
+            (Some(ColumnIdentity::Serial), DataType::BigInt(_)) => "BIGSERIAL".to_string(),
+            (Some(ColumnIdentity::Serial), _) => "SERIAL".to_string(),
+            _ => self.get_type_ddl(),
+        };
+        let mut column_options = vec![utils::quote_identifier(&self.name), sql_type];
+        if let Some(generation_expression) = &self.generation_expression {
+            column_options.push(format!(
+                "GENERATED ALWAYS AS ({generation_expression}) STORED"
+            ));
+        } else if self.identity == Some(ColumnIdentity::Always) {
+            column_options.push("GENERATED ALWAYS AS IDENTITY".to_string());
+        } else if self.identity == Some(ColumnIdentity::ByDefault) {
+            column_options.push("GENERATED BY DEFAULT AS IDENTITY".to_string());
+        } else if let Some(default_value) = &self.default_value {
+            column_options.push(format!("DEFAULT {default_value}"));
+        }
+        if self.is_not_null {
+            column_options.push("NOT NULL".to_string());
+        }
+        if self.is_unique && !self.is_primary_key {
+            column_options.push("UNIQUE".to_string());
+        }
+        if self.is_primary_key {
+            column_options.push("PRIMARY KEY".to_string());
+        }
+        if let Some(allowed_values) = &self.allowed_values {
+            let allowed_values = allowed_values
+                .iter()
+                .map(|value| utils::quote_string(value))
+                .collect::<Vec<String>>()
+                .join(", ");
+            column_options.push(format!(
+                "CHECK ({} IN ({allowed_values}))",
+                utils::quote_identifier(&self.name)
+            ));
+        }
+        column_options.join(" ")
+    }
+
+    // Generated columns and the `GENERATED ALWAYS AS IDENTITY` columns can't
+    // be written in the INSERT statement.
+    pub fn is_insertable(&self) -> bool {
+        self.generation_expression.is_none() && self.identity != Some(ColumnIdentity::Always)
+    }
+
+    // Column can be omitted in the INSERT statement when the database fills
+    // it in by itself.
+    pub fn is_optional_on_insert(&self) -> bool {
+        !self.is_not_null || self.default_value.is_some() || self.identity.is_some()
     }
 }
@@ -0,0 +1,15 @@
+use super::ColumnIdentity;
+
+// Parts of the column definition that are extracted from the DDL before it is
+// handed over to the SQL parser.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnAttributes {
+    pub default_value: Option<String>,
+    pub identity: Option<ColumnIdentity>,
+    pub generation_expression: Option<String>,
+    pub array_dimensions: usize,
+    pub alias: Option<String>,
+    pub allowed_values: Option<Vec<String>>,
+    pub type_annotation: Option<String>,
+    pub description: Option<String>,
+}
@@ -1,6 +1,9 @@
-use super::table::{self, Table};
-use crate::error;
-use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
+use super::table::Table;
+use super::{AlterTableAction, Column, EnumType, Function, Migration, SchemaChange};
+use crate::dialects::Dialect;
+use crate::lexer::{self, TokenKind};
+use crate::{error, utils};
+use sqlparser::ast::DataType;
 use std::fs;
 use std::path::PathBuf;
 
@@ -8,40 +11,312 @@ use std::path::PathBuf;
 pub struct Database {
     pub name: String,
     pub tables: Vec<Table>,
+    pub enum_types: Vec<EnumType>,
+    pub functions: Vec<Function>,
+    // The partitions folded under their parent tables, the statements
+    // targeting them, like the pg_dump's indexes, are skipped.
+    pub partition_names: Vec<String>,
 }
 
 impl PartialEq for Database {
     fn eq(&self, other: &Database) -> bool {
-        self.name == other.name && self.tables == other.tables
+        self.name == other.name
+            && self.tables == other.tables
+            && self.enum_types == other.enum_types
+            && self.functions == other.functions
+            && self.partition_names == other.partition_names
     }
 }
 
 impl Database {
     pub fn new(name: String, tables: Vec<Table>) -> Database {
-        Database { name, tables }
+        Database {
+            name,
+            tables,
+            enum_types: vec![],
+            functions: vec![],
+            partition_names: vec![],
+        }
     }
 
     pub fn from_schema_file_path(
+        dialect: &dyn Dialect,
         schema_file_path: PathBuf,
     ) -> Result<Database, error::CodegenError> {
-        let dialect = PostgreSqlDialect {};
-        let schema_ddl = fs::read_to_string(schema_file_path)?;
-        let schema_ast = Parser::parse_sql(&dialect, &schema_ddl)?;
-        Database::from_ast(&schema_ast)
+        // The schema file isn't a migration, a `-- migrate:down` line in it
+        // is only a comment.
+        Database::from_schema_ddl(dialect, &fs::read_to_string(schema_file_path)?)
     }
 
-    fn from_ast(ast: &Vec<Statement>) -> Result<Database, error::CodegenError> {
-        let tables = ast
-            .iter()
-            .filter_map(|statement| match statement {
-                Statement::CreateTable { .. } => Some(Table::from_statement(statement)),
+    pub fn from_schema_ddl(
+        dialect: &dyn Dialect,
+        ddl: &str,
+    ) -> Result<Database, error::CodegenError> {
+        let mut database = Database::new("public".to_string(), vec![]);
+        database.apply_ddl(dialect, ddl)?;
+        Ok(database)
+    }
+
+    // Builds the database by replaying the migrations in the given order.
+    pub fn from_migration_file_paths(
+        dialect: &dyn Dialect,
+        migration_file_paths: Vec<PathBuf>,
+    ) -> Result<Database, error::CodegenError> {
+        let mut database = Database::new("public".to_string(), vec![]);
+        for migration_file_path in migration_file_paths {
+            let migration = Migration::from_file_path(&migration_file_path)?;
+            database.apply_ddl(dialect, &migration.up)?;
+        }
+        Ok(database)
+    }
+
+    pub fn apply_ddl(
+        &mut self,
+        dialect: &dyn Dialect,
+        ddl: &str,
+    ) -> Result<(), error::CodegenError> {
+        let mut tokens = dialect.tokenize(ddl)?;
+        if dialect.folds_identifiers_to_lower_case() {
+            for token in &mut tokens {
+                if token.kind == TokenKind::Word {
+                    token.text = token.text.to_lowercase();
+                }
+            }
+        }
+        for statement_tokens in lexer::split_statements(tokens) {
+            if let Some(schema_change) = SchemaChange::from_tokens(dialect, &statement_tokens)? {
+                self.apply_schema_change(schema_change)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply_schema_change(
+        &mut self,
+        schema_change: SchemaChange,
+    ) -> Result<(), error::CodegenError> {
+        match schema_change {
+            SchemaChange::CreateTable {
+                mut table,
+                if_not_exists,
+                inherited_table_names,
+            } => {
+                if self.has_table(&table.name) {
+                    if if_not_exists {
+                        return Ok(());
+                    }
+                    return Err(error::CodegenError::SchemaError(format!(
+                        "Table \"{}\" already exists",
+                        table.name
+                    )));
+                }
+                if !inherited_table_names.is_empty() {
+                    table.columns =
+                        self.get_inherited_columns(&inherited_table_names, table.columns)?;
+                }
+                self.tables.push(table);
+            }
+            SchemaChange::AlterTable {
+                table_name,
+                if_exists,
+                actions,
+            } => {
+                if self.is_partition(&table_name) {
+                    return Ok(());
+                }
+                let table = match self.find_table_mut(&table_name) {
+                    Some(table) => table,
+                    None if if_exists => return Ok(()),
+                    None => return Err(Database::table_not_found(&table_name)),
+                };
+                let mut partition_names = vec![];
+                for action in actions {
+                    if let AlterTableAction::AttachPartition { partition_name } = &action {
+                        partition_names.push(partition_name.clone());
+                    }
+                    table.apply_alter_table_action(action)?;
+                }
+                self.tables
+                    .retain(|table| !partition_names.contains(&table.name));
+                self.partition_names.extend(partition_names);
+            }
+            SchemaChange::DropTable {
+                table_names,
+                if_exists,
+            } => {
+                for table_name in table_names {
+                    if self.is_partition(&table_name) {
+                        self.partition_names.retain(|name| *name != table_name);
+                        continue;
+                    }
+                    if !self.has_table(&table_name) && !if_exists {
+                        return Err(Database::table_not_found(&table_name));
+                    }
+                    self.tables.retain(|table| table.name != table_name);
+                }
+            }
+            SchemaChange::CreateIndex {
+                table_name,
+                column_names,
+                is_unique,
+            } => {
+                if self.is_partition(&table_name) {
+                    return Ok(());
+                }
+                let table = self
+                    .find_table_mut(&table_name)
+                    .ok_or_else(|| Database::table_not_found(&table_name))?;
+                // Only the unique index on a single column makes the column unique.
+                if is_unique && column_names.len() == 1 {
+                    let column = table.find_column_mut(&column_names[0])?;
+                    column.is_unique = true;
+                }
+            }
+            SchemaChange::CreateType { enum_type } => {
+                self.enum_types
+                    .retain(|other_enum_type| other_enum_type.name != enum_type.name);
+                self.enum_types.push(enum_type);
+            }
+            SchemaChange::CommentOnTable {
+                table_name,
+                description,
+            } => {
+                if self.is_partition(&table_name) {
+                    return Ok(());
+                }
+                let table = self
+                    .find_table_mut(&table_name)
+                    .ok_or_else(|| Database::table_not_found(&table_name))?;
+                table.description = description;
+            }
+            SchemaChange::CommentOnColumn {
+                table_name,
+                column_name,
+                description,
+            } => {
+                if self.is_partition(&table_name) {
+                    return Ok(());
+                }
+                let table = self
+                    .find_table_mut(&table_name)
+                    .ok_or_else(|| Database::table_not_found(&table_name))?;
+                let column = table.find_column_mut(&column_name)?;
+                // The `@type` annotation in the comment survives the schema
+                // downloads, unlike the one in the schema file.
+                if let Some(type_annotation) = description
+                    .as_ref()
+                    .and_then(|description| lexer::find_annotation_text(description, "type"))
+                {
+                    column.type_annotation = Some(type_annotation);
+                }
+                column.description = description;
+            }
+            SchemaChange::CreateFunction { mut function } => {
+                self.resolve_function_result(&mut function);
+                // The function with the same signature is replaced, the other
+                // ones are the overloads.
+                self.functions.retain(|other_function| {
+                    other_function.name != function.name
+                        || !other_function.has_argument_types(&function.arguments)
+                });
+                self.functions.push(function);
+            }
+            SchemaChange::DropFunction {
+                functions,
+                if_exists,
+            } => {
+                for (function_name, arguments) in functions {
+                    let is_dropped = |function: &Function| {
+                        function.name == function_name
+                            && match &arguments {
+                                Some(arguments) => function.has_argument_types(arguments),
+                                None => true,
+                            }
+                    };
+                    if !self.functions.iter().any(is_dropped) && !if_exists {
+                        return Err(error::CodegenError::SchemaError(format!(
+                            "Function \"{function_name}\" not found"
+                        )));
+                    }
+                    self.functions.retain(|function| !is_dropped(function));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // The function returning the table type returns the rows of the table.
+    fn resolve_function_result(&self, function: &mut Function) {
+        let table = match function.get_scalar_result_column() {
+            Some(column) if column.array_dimensions == 0 => match &column.sql_type {
+                DataType::Custom(name) => self.find_table(&utils::object_name_to_string(name)),
                 _ => None,
-            })
-            .collect::<Result<Vec<table::Table>, error::CodegenError>>()?;
-        Ok(Database::new("public".to_string(), tables))
+            },
+            _ => None,
+        };
+        if let Some(table) = table {
+            function.result_table.columns = table.columns.clone();
+        }
+    }
+
+    // The inherited columns come first, in the order of the parents, and the
+    // local column with the name of the inherited one is merged into it. The
+    // NOT NULL constraints are inherited, unlike the unique ones.
+    fn get_inherited_columns(
+        &self,
+        inherited_table_names: &[String],
+        local_columns: Vec<Column>,
+    ) -> Result<Vec<Column>, error::CodegenError> {
+        let mut columns: Vec<Column> = vec![];
+        for table_name in inherited_table_names {
+            let table = self
+                .find_table(table_name)
+                .ok_or_else(|| Database::table_not_found(table_name))?;
+            for column in &table.columns {
+                if columns
+                    .iter()
+                    .any(|other_column| other_column.name == column.name)
+                {
+                    continue;
+                }
+                let column = match local_columns
+                    .iter()
+                    .find(|local_column| local_column.name == column.name)
+                {
+                    Some(local_column) => {
+                        let mut local_column = local_column.clone();
+                        local_column.is_not_null |= column.is_not_null;
+                        local_column
+                    }
+                    None => {
+                        let mut column = column.clone();
+                        column.is_primary_key = false;
+                        column.is_unique = false;
+                        column
+                    }
+                };
+                columns.push(column);
+            }
+        }
+        for local_column in local_columns {
+            if !columns
+                .iter()
+                .any(|column| column.name == local_column.name)
+            {
+                columns.push(local_column);
+            }
+        }
+        Ok(columns)
+    }
+
+    fn table_not_found(table_name: &str) -> error::CodegenError {
+        error::CodegenError::SchemaError(format!("Table \"{table_name}\" not found"))
+    }
+
+    fn is_partition(&self, table_name: &str) -> bool {
+        self.partition_names.iter().any(|name| name == table_name)
     }
 
-    #[allow(dead_code)]
     pub fn has_table(&self, table_name: &str) -> bool {
         self.tables.iter().any(|table| table.name == table_name)
     }
@@ -49,4 +324,307 @@ impl Database {
     pub fn find_table(&self, table_name: &str) -> Option<&Table> {
         self.tables.iter().find(|table| table.name == table_name)
     }
+
+    pub fn find_table_mut(&mut self, table_name: &str) -> Option<&mut Table> {
+        self.tables
+            .iter_mut()
+            .find(|table| table.name == table_name)
+    }
+
+    // Returns the overload of the function accepting the number of the
+    // arguments, the overloads taking the same number of the arguments can't
+    // be told apart.
+    pub fn find_function(
+        &self,
+        function_name: &str,
+        argument_count: usize,
+    ) -> Result<Option<&Function>, error::CodegenError> {
+        let functions = self
+            .functions
+            .iter()
+            .filter(|function| function.name == function_name)
+            .collect::<Vec<&Function>>();
+        if functions.is_empty() {
+            return Ok(None);
+        }
+        let overloads = functions
+            .into_iter()
+            .filter(|function| function.accepts_argument_count(argument_count))
+            .collect::<Vec<&Function>>();
+        match overloads[..] {
+            [function] => Ok(Some(function)),
+            [] => Err(error::CodegenError::QueryError(format!(
+                "Function \"{function_name}\" doesn't accept {argument_count} arguments"
+            ))),
+            _ => Err(error::CodegenError::QueryError(format!(
+                "Function \"{function_name}\" has multiple overloads accepting {argument_count} arguments"
+            ))),
+        }
+    }
+
+    pub fn find_enum_type(&self, enum_type_name: &str) -> Option<&EnumType> {
+        self.enum_types
+            .iter()
+            .find(|enum_type| enum_type.name == enum_type_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::PostgresDialect;
+
+    #[test]
+    fn fold_pg_dump_partitions() {
+        let database = Database::from_schema_ddl(
+            &PostgresDialect::new(),
+            "
+            CREATE TABLE public.events (
+                id integer NOT NULL,
+                created_at timestamp with time zone NOT NULL,
+                payload jsonb
+            )
+            PARTITION BY RANGE (created_at);
+
+            CREATE TABLE public.events_2024 (
+                id integer NOT NULL,
+                created_at timestamp with time zone NOT NULL,
+                payload jsonb
+            );
+
+            ALTER TABLE public.events_2024 OWNER TO postgres;
+            ALTER TABLE ONLY public.events ATTACH PARTITION public.events_2024 FOR VALUES FROM ('2024-01-01 00:00:00+00') TO ('2025-01-01 00:00:00+00');
+            CREATE TABLE public.events_2025 PARTITION OF public.events FOR VALUES FROM ('2025-01-01 00:00:00+00') TO ('2026-01-01 00:00:00+00');
+            COMMENT ON TABLE public.events_2024 IS 'Events of 2024';
+            COMMENT ON COLUMN public.events_2024.payload IS 'Event payload';
+            ALTER TABLE ONLY public.events ADD CONSTRAINT events_pkey PRIMARY KEY (id, created_at);
+            ALTER TABLE ONLY public.events_2024 ADD CONSTRAINT events_2024_pkey PRIMARY KEY (id, created_at);
+            CREATE INDEX events_created_at_idx ON ONLY public.events USING btree (created_at);
+            CREATE INDEX events_2024_created_at_idx ON public.events_2024 USING btree (created_at);
+            ALTER INDEX public.events_created_at_idx ATTACH PARTITION public.events_2024_created_at_idx;
+            ",
+        )
+        .unwrap();
+        let table_names = database
+            .tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(table_names, ["events"]);
+        assert_eq!(database.partition_names, ["events_2024", "events_2025"]);
+        let table = database.find_table("events").unwrap();
+        assert!(table.find_column("id").unwrap().is_primary_key);
+        assert_eq!(table.description, None);
+    }
+
+    #[test]
+    fn replay_migrations() {
+        let dialect = PostgresDialect::new();
+        let mut database = Database::from_schema_ddl(
+            &dialect,
+            "
+            CREATE TYPE status AS ENUM ('active');
+            CREATE TABLE users (id integer PRIMARY KEY, name text, nickname text, email text NOT NULL);
+            CREATE TABLE sessions (id integer);
+            ",
+        )
+        .unwrap();
+        for ddl in [
+            "CREATE TABLE IF NOT EXISTS users (id integer);",
+            "CREATE TYPE status AS ENUM ('active', 'disabled');",
+            "ALTER TABLE users ADD COLUMN status status NOT NULL;",
+            "ALTER TABLE users ADD COLUMN IF NOT EXISTS status text;",
+            "ALTER TABLE users DROP COLUMN nickname;",
+            "ALTER TABLE users DROP COLUMN IF EXISTS nickname;",
+            "ALTER TABLE users RENAME COLUMN name TO full_name;",
+            "ALTER TABLE users ALTER COLUMN id TYPE bigint;",
+            "ALTER TABLE users ALTER COLUMN full_name SET NOT NULL;",
+            "ALTER TABLE users ALTER COLUMN email DROP NOT NULL;",
+            "CREATE UNIQUE INDEX users_email_idx ON users (email);",
+            "DROP TABLE sessions;",
+            "DROP TABLE IF EXISTS sessions;",
+            "ALTER TABLE IF EXISTS sessions ADD COLUMN token text;",
+        ] {
+            database.apply_ddl(&dialect, ddl).unwrap();
+        }
+
+        assert_eq!(database.tables.len(), 1);
+        let table = database.find_table("users").unwrap();
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name.as_str(),
+                    column.sql_type.to_string(),
+                    column.is_not_null,
+                    column.is_unique,
+                )
+            })
+            .collect::<Vec<(&str, String, bool, bool)>>();
+        assert_eq!(
+            columns,
+            [
+                ("id", "BIGINT".to_string(), true, true),
+                ("full_name", "TEXT".to_string(), true, false),
+                ("email", "TEXT".to_string(), false, true),
+                ("status", "status".to_string(), true, false),
+            ]
+        );
+        assert!(table.find_column("id").unwrap().is_primary_key);
+        assert_eq!(
+            database.find_enum_type("status").unwrap().values,
+            ["active", "disabled"]
+        );
+    }
+
+    #[test]
+    fn fold_unquoted_identifiers() {
+        let dialect = PostgresDialect::new();
+        let mut database =
+            Database::from_schema_ddl(&dialect, "CREATE TABLE Users (Id integer, \"Name\" text);")
+                .unwrap();
+        database
+            .apply_ddl(&dialect, "ALTER TABLE users ADD COLUMN Email text;")
+            .unwrap();
+        database
+            .apply_ddl(&dialect, "ALTER TABLE USERS RENAME COLUMN ID TO user_id;")
+            .unwrap();
+        let table = database.find_table("users").unwrap();
+        let column_names = table
+            .columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(column_names, vec!["user_id", "Name", "email"]);
+        assert!(database
+            .apply_ddl(&dialect, "ALTER TABLE \"Users\" ADD COLUMN age integer;")
+            .is_err());
+    }
+
+    #[test]
+    fn reject_migrations_on_missing_tables() {
+        let dialect = PostgresDialect::new();
+        for ddl in [
+            "CREATE TABLE users (id integer);",
+            "ALTER TABLE sessions ADD COLUMN token text;",
+            "ALTER TABLE users ADD COLUMN id integer;",
+            "ALTER TABLE users DROP COLUMN email;",
+            "ALTER TABLE users RENAME COLUMN email TO address;",
+            "ALTER TABLE users ALTER COLUMN email SET NOT NULL;",
+            "DROP TABLE sessions;",
+            "CREATE INDEX sessions_token_idx ON sessions (token);",
+            "CREATE UNIQUE INDEX users_email_idx ON users (email);",
+        ] {
+            let mut database =
+                Database::from_schema_ddl(&dialect, "CREATE TABLE users (id integer);").unwrap();
+            assert!(database.apply_ddl(&dialect, ddl).is_err(), "{ddl}");
+        }
+    }
+
+    #[test]
+    fn add_inherited_columns() {
+        let database = Database::from_schema_ddl(
+            &PostgresDialect::new(),
+            "
+            CREATE TABLE public.vehicles (
+                id integer NOT NULL,
+                name text
+            );
+
+            CREATE TABLE public.cars (
+                name text NOT NULL,
+                doors integer
+            )
+            INHERITS (public.vehicles);
+
+            ALTER TABLE ONLY public.vehicles ADD CONSTRAINT vehicles_pkey PRIMARY KEY (id);
+            ",
+        )
+        .unwrap();
+        let table = database.find_table("cars").unwrap();
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.is_not_null))
+            .collect::<Vec<(&str, bool)>>();
+        assert_eq!(columns, [("id", true), ("name", true), ("doors", false)]);
+        assert!(!table.find_column("id").unwrap().is_primary_key);
+
+        let mut database = database;
+        assert!(database
+            .apply_ddl(
+                &PostgresDialect::new(),
+                "CREATE TABLE trucks (axles integer) INHERITS (missing);"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn tell_overloaded_functions_apart() {
+        let dialect = PostgresDialect::new();
+        let mut database = Database::from_schema_ddl(
+            &dialect,
+            "
+            CREATE FUNCTION add(a integer, b integer) RETURNS integer LANGUAGE sql AS $$ SELECT a + b $$;
+            CREATE FUNCTION add(a numeric, b numeric, c numeric DEFAULT 0) RETURNS numeric LANGUAGE sql AS $$ SELECT a + b + c $$;
+            CREATE OR REPLACE FUNCTION add(a integer, b integer) RETURNS bigint LANGUAGE sql AS $$ SELECT a + b $$;
+            CREATE FUNCTION greet(name text, greeting text DEFAULT 'Hello') RETURNS text LANGUAGE sql AS $$ SELECT greeting || name $$;
+            ",
+        )
+        .unwrap();
+        assert_eq!(database.functions.len(), 3);
+        let result_type = |database: &Database, name: &str, argument_count: usize| {
+            database
+                .find_function(name, argument_count)
+                .unwrap()
+                .map(|function| {
+                    function
+                        .get_scalar_result_column()
+                        .unwrap()
+                        .sql_type
+                        .clone()
+                })
+        };
+        assert_eq!(
+            result_type(&database, "add", 3),
+            Some(DataType::Decimal(None, None))
+        );
+        assert_eq!(result_type(&database, "greet", 1), Some(DataType::Text));
+        assert_eq!(result_type(&database, "greet", 2), Some(DataType::Text));
+        assert_eq!(result_type(&database, "missing", 0), None);
+        assert!(database.find_function("add", 2).is_err());
+        assert!(database.find_function("greet", 3).is_err());
+
+        database
+            .apply_ddl(&dialect, "DROP FUNCTION add(numeric, numeric, numeric);")
+            .unwrap();
+        assert_eq!(
+            result_type(&database, "add", 2),
+            Some(DataType::BigInt(None))
+        );
+        assert!(database
+            .apply_ddl(&dialect, "DROP FUNCTION add(text);")
+            .is_err());
+        database
+            .apply_ddl(&dialect, "DROP FUNCTION greet;")
+            .unwrap();
+        assert_eq!(database.functions.len(), 1);
+    }
+
+    #[test]
+    fn reject_ambiguous_overloads() {
+        let dialect = PostgresDialect::new();
+        let database = Database::from_schema_ddl(
+            &dialect,
+            "
+            CREATE FUNCTION f(a integer) RETURNS integer LANGUAGE sql AS $$ SELECT a $$;
+            CREATE FUNCTION f(a text) RETURNS text LANGUAGE sql AS $$ SELECT a $$;
+            ",
+        )
+        .unwrap();
+        assert_eq!(database.functions.len(), 2);
+        assert!(database.find_function("f", 1).is_err());
+        assert!(database.find_function("f", 2).is_err());
+    }
 }
@@ -0,0 +1,533 @@
+use super::{Column, Database, EnumType, Table};
+use crate::dialects::Dialect;
+use crate::utils;
+use sqlparser::ast::DataType;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum SchemaDifference {
+    AddTable {
+        table: Table,
+    },
+    DropTable {
+        table_name: String,
+    },
+    AddColumn {
+        table_name: String,
+        column: Column,
+    },
+    DropColumn {
+        table_name: String,
+        column_name: String,
+    },
+    AlterColumnType {
+        table_name: String,
+        column_name: String,
+        old_type: String,
+        new_type: String,
+    },
+    AlterColumnNullability {
+        table_name: String,
+        column_name: String,
+        is_not_null: bool,
+    },
+    CreateEnumType {
+        enum_type: EnumType,
+    },
+    DropEnumType {
+        type_name: String,
+    },
+    AlterEnumType {
+        type_name: String,
+        added_values: Vec<String>,
+        removed_values: Vec<String>,
+    },
+}
+
+impl SchemaDifference {
+    // Types are created before the tables using them and dropped after.
+    fn get_order(&self) -> usize {
+        match self {
+            SchemaDifference::CreateEnumType { .. } => 0,
+            SchemaDifference::AlterEnumType { .. } => 1,
+            SchemaDifference::AddTable { .. } => 2,
+            SchemaDifference::AddColumn { .. } => 3,
+            SchemaDifference::AlterColumnType { .. } => 4,
+            SchemaDifference::AlterColumnNullability { .. } => 5,
+            SchemaDifference::DropColumn { .. } => 6,
+            SchemaDifference::DropTable { .. } => 7,
+            SchemaDifference::DropEnumType { .. } => 8,
+        }
+    }
+
+    // Returns the reason when the change can lose data.
+    pub fn get_destructive_reason(&self) -> Option<&'static str> {
+        match self {
+            SchemaDifference::DropTable { .. } => Some("drops the table with its data"),
+            SchemaDifference::DropColumn { .. } => Some("drops the column with its data"),
+            SchemaDifference::AlterColumnType { .. } => {
+                Some("converting the existing values can fail or lose precision")
+            }
+            SchemaDifference::DropEnumType { .. } => Some("drops the type"),
+            SchemaDifference::AlterEnumType { removed_values, .. }
+                if !removed_values.is_empty() =>
+            {
+                Some("values can't be removed from the enum type, the type has to be recreated")
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_ddl(&self) -> String {
+        match self {
+            SchemaDifference::AddTable { table } => {
+                let columns = table
+                    .columns
+                    .iter()
+                    .map(|column| format!("\t{}", column.get_definition_ddl()))
+                    .collect::<Vec<String>>()
+                    .join(",\n");
+                format!(
+                    "CREATE TABLE {} (\n{columns}\n);",
+                    utils::quote_identifier(&table.name)
+                )
+            }
+            SchemaDifference::DropTable { table_name } => {
+                format!("DROP TABLE {};", utils::quote_identifier(table_name))
+            }
+            SchemaDifference::AddColumn { table_name, column } => format!(
+                "ALTER TABLE {} ADD COLUMN {};",
+                utils::quote_identifier(table_name),
+                column.get_definition_ddl()
+            ),
+            SchemaDifference::DropColumn {
+                table_name,
+                column_name,
+            } => format!(
+                "ALTER TABLE {} DROP COLUMN {};",
+                utils::quote_identifier(table_name),
+                utils::quote_identifier(column_name)
+            ),
+            SchemaDifference::AlterColumnType {
+                table_name,
+                column_name,
+                new_type,
+                ..
+            } => {
+                // Postgres only converts the values implicitly between some of
+                // the types, like `integer` to `bigint`.
+                let column_name = utils::quote_identifier(column_name);
+                format!(
+                    "ALTER TABLE {} ALTER COLUMN {column_name} TYPE {new_type} USING {column_name}::{new_type};",
+                    utils::quote_identifier(table_name)
+                )
+            }
+            SchemaDifference::AlterColumnNullability {
+                table_name,
+                column_name,
+                is_not_null,
+            } => format!(
+                "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL;",
+                utils::quote_identifier(table_name),
+                utils::quote_identifier(column_name),
+                if *is_not_null { "SET" } else { "DROP" }
+            ),
+            SchemaDifference::CreateEnumType { enum_type } => format!(
+                "CREATE TYPE {} AS ENUM ({});",
+                utils::quote_identifier(&enum_type.name),
+                enum_type
+                    .values
+                    .iter()
+                    .map(|value| utils::quote_string(value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            SchemaDifference::DropEnumType { type_name } => {
+                format!("DROP TYPE {};", utils::quote_identifier(type_name))
+            }
+            SchemaDifference::AlterEnumType {
+                type_name,
+                added_values,
+                removed_values,
+            } => {
+                let mut statements = added_values
+                    .iter()
+                    .map(|value| {
+                        format!(
+                            "ALTER TYPE {} ADD VALUE {};",
+                            utils::quote_identifier(type_name),
+                            utils::quote_string(value)
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                if !removed_values.is_empty() {
+                    let values = removed_values
+                        .iter()
+                        .map(|value| utils::quote_string(value))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    // The statement is left to the author, like the data of the
+                    // columns using the values.
+                    statements.push(format!("-- Recreate the type without the {values} values."));
+                }
+                statements.join("\n")
+            }
+        }
+    }
+}
+
+impl fmt::Display for SchemaDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaDifference::AddTable { table } => write!(f, "+ table \"{}\"", table.name),
+            SchemaDifference::DropTable { table_name } => write!(f, "- table \"{table_name}\""),
+            SchemaDifference::AddColumn { table_name, column } => write!(
+                f,
+                "+ column \"{table_name}\".\"{}\" {}",
+                column.name,
+                column.get_type_ddl()
+            ),
+            SchemaDifference::DropColumn {
+                table_name,
+                column_name,
+            } => write!(f, "- column \"{table_name}\".\"{column_name}\""),
+            SchemaDifference::AlterColumnType {
+                table_name,
+                column_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "~ column \"{table_name}\".\"{column_name}\" type {old_type} -> {new_type}"
+            ),
+            SchemaDifference::AlterColumnNullability {
+                table_name,
+                column_name,
+                is_not_null,
+            } => write!(
+                f,
+                "~ column \"{table_name}\".\"{column_name}\" {}",
+                if *is_not_null {
+                    "NULL -> NOT NULL"
+                } else {
+                    "NOT NULL -> NULL"
+                }
+            ),
+            SchemaDifference::CreateEnumType { enum_type } => {
+                write!(f, "+ type \"{}\"", enum_type.name)
+            }
+            SchemaDifference::DropEnumType { type_name } => write!(f, "- type \"{type_name}\""),
+            SchemaDifference::AlterEnumType {
+                type_name,
+                added_values,
+                removed_values,
+            } => {
+                write!(f, "~ type \"{type_name}\"")?;
+                for value in added_values {
+                    write!(f, " +'{value}'")?;
+                }
+                for value in removed_values {
+                    write!(f, " -'{value}'")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// Structural differences between the databases, listed as the changes that
+// turn the old database into the new one.
+#[derive(Debug)]
+pub struct DatabaseDiff {
+    pub differences: Vec<SchemaDifference>,
+}
+
+impl DatabaseDiff {
+    pub fn new(
+        dialect: &dyn Dialect,
+        old_database: &Database,
+        new_database: &Database,
+    ) -> DatabaseDiff {
+        let mut differences = vec![];
+        // Materialized views are not created by the table statements.
+        for old_table in &old_database.tables {
+            if old_table.is_materialized_view {
+                continue;
+            }
+            match new_database.find_table(&old_table.name) {
+                Some(new_table) if new_table.is_materialized_view => {}
+                Some(new_table) => {
+                    DatabaseDiff::diff_tables(dialect, old_table, new_table, &mut differences);
+                }
+                None => differences.push(SchemaDifference::DropTable {
+                    table_name: old_table.name.clone(),
+                }),
+            }
+        }
+        for new_table in &new_database.tables {
+            if !new_table.is_materialized_view && !old_database.has_table(&new_table.name) {
+                differences.push(SchemaDifference::AddTable {
+                    table: new_table.clone(),
+                });
+            }
+        }
+        for old_enum_type in &old_database.enum_types {
+            match new_database.find_enum_type(&old_enum_type.name) {
+                Some(new_enum_type) if new_enum_type.values != old_enum_type.values => {
+                    let values_not_in = |values: &[String], other_values: &[String]| {
+                        values
+                            .iter()
+                            .filter(|value| !other_values.contains(value))
+                            .cloned()
+                            .collect::<Vec<String>>()
+                    };
+                    differences.push(SchemaDifference::AlterEnumType {
+                        type_name: old_enum_type.name.clone(),
+                        added_values: values_not_in(&new_enum_type.values, &old_enum_type.values),
+                        removed_values: values_not_in(&old_enum_type.values, &new_enum_type.values),
+                    });
+                }
+                Some(_) => {}
+                None => differences.push(SchemaDifference::DropEnumType {
+                    type_name: old_enum_type.name.clone(),
+                }),
+            }
+        }
+        for new_enum_type in &new_database.enum_types {
+            if old_database.find_enum_type(&new_enum_type.name).is_none() {
+                differences.push(SchemaDifference::CreateEnumType {
+                    enum_type: new_enum_type.clone(),
+                });
+            }
+        }
+        DatabaseDiff { differences }
+    }
+
+    // The types the SQL parser reads as the custom ones, like `bool`, are
+    // compared by their names, the other ones together with their parameters.
+    fn is_same_type(dialect: &dyn Dialect, old_column: &Column, new_column: &Column) -> bool {
+        let is_custom = |column: &Column| matches!(column.sql_type, DataType::Custom(_));
+        let is_same_sql_type = if is_custom(old_column) || is_custom(new_column) {
+            dialect
+                .get_sql_type_name(&old_column.sql_type)
+                .eq_ignore_ascii_case(&dialect.get_sql_type_name(&new_column.sql_type))
+        } else {
+            old_column.sql_type == new_column.sql_type
+        };
+        is_same_sql_type && old_column.array_dimensions == new_column.array_dimensions
+    }
+
+    fn diff_tables(
+        dialect: &dyn Dialect,
+        old_table: &Table,
+        new_table: &Table,
+        differences: &mut Vec<SchemaDifference>,
+    ) {
+        let table_name = &new_table.name;
+        for old_column in &old_table.columns {
+            let new_column = match new_table.find_column(&old_column.name) {
+                Some(new_column) => new_column,
+                None => {
+                    differences.push(SchemaDifference::DropColumn {
+                        table_name: table_name.clone(),
+                        column_name: old_column.name.clone(),
+                    });
+                    continue;
+                }
+            };
+            if !DatabaseDiff::is_same_type(dialect, old_column, new_column) {
+                differences.push(SchemaDifference::AlterColumnType {
+                    table_name: table_name.clone(),
+                    column_name: new_column.name.clone(),
+                    old_type: old_column.get_type_ddl(),
+                    new_type: new_column.get_type_ddl(),
+                });
+            }
+            if old_column.is_not_null != new_column.is_not_null {
+                differences.push(SchemaDifference::AlterColumnNullability {
+                    table_name: table_name.clone(),
+                    column_name: new_column.name.clone(),
+                    is_not_null: new_column.is_not_null,
+                });
+            }
+        }
+        for new_column in &new_table.columns {
+            if !old_table.has_column(&new_column.name) {
+                differences.push(SchemaDifference::AddColumn {
+                    table_name: table_name.clone(),
+                    column: new_column.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        self.differences
+            .iter()
+            .any(|difference| difference.get_destructive_reason().is_some())
+    }
+
+    // Returns the statements applying the differences in a working order, the
+    // destructive ones are preceded by a warning comment.
+    pub fn to_ddl(&self) -> String {
+        let mut differences = self.differences.iter().collect::<Vec<&SchemaDifference>>();
+        differences.sort_by_key(|difference| difference.get_order());
+        differences
+            .iter()
+            .map(|difference| match difference.get_destructive_reason() {
+                Some(reason) => format!(
+                    "-- WARNING: Destructive change, {reason}.\n{}\n",
+                    difference.to_ddl()
+                ),
+                None => format!("{}\n", difference.to_ddl()),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl fmt::Display for DatabaseDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for difference in &self.differences {
+            writeln!(f, "{difference}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::PostgresDialect;
+    use sqlparser::ast::{Ident, ObjectName};
+
+    fn create_column(name: &str, sql_type: DataType, is_not_null: bool) -> Column {
+        Column::new(name.to_string(), sql_type, false, false, is_not_null, None)
+    }
+
+    #[test]
+    fn diff_databases() {
+        let old_database = Database::new(
+            "public".to_string(),
+            vec![
+                Table::new(
+                    "users".to_string(),
+                    vec![
+                        create_column("name", DataType::Int(None), false),
+                        create_column("nickname", DataType::Text, false),
+                    ],
+                ),
+                Table::new("sessions".to_string(), vec![]),
+            ],
+        );
+        let mut tags_column = create_column("tags", DataType::Text, false);
+        tags_column.array_dimensions = 1;
+        let new_database = Database::new(
+            "public".to_string(),
+            vec![Table::new(
+                "users".to_string(),
+                vec![create_column("name", DataType::Text, true), tags_column],
+            )],
+        );
+        let diff = DatabaseDiff::new(&PostgresDialect::new(), &old_database, &new_database);
+        assert_eq!(
+            diff.to_string(),
+            "\
+            ~ column \"users\".\"name\" type INT -> TEXT\n\
+            ~ column \"users\".\"name\" NULL -> NOT NULL\n\
+            - column \"users\".\"nickname\"\n\
+            + column \"users\".\"tags\" TEXT[]\n\
+            - table \"sessions\"\n"
+        );
+        assert_eq!(
+            diff.to_ddl(),
+            "\
+            ALTER TABLE \"users\" ADD COLUMN \"tags\" TEXT[];\n\n\
+            -- WARNING: Destructive change, converting the existing values can fail or lose precision.\n\
+            ALTER TABLE \"users\" ALTER COLUMN \"name\" TYPE TEXT USING \"name\"::TEXT;\n\n\
+            ALTER TABLE \"users\" ALTER COLUMN \"name\" SET NOT NULL;\n\n\
+            -- WARNING: Destructive change, drops the column with its data.\n\
+            ALTER TABLE \"users\" DROP COLUMN \"nickname\";\n\n\
+            -- WARNING: Destructive change, drops the table with its data.\n\
+            DROP TABLE \"sessions\";\n"
+        );
+    }
+
+    #[test]
+    fn skip_materialized_views() {
+        let dialect = PostgresDialect::new();
+        let database =
+            Database::from_schema_ddl(&dialect, "CREATE TABLE users (id integer NOT NULL);")
+                .unwrap();
+        let live_database = Database::from_schema_ddl(
+            &dialect,
+            "
+            CREATE TABLE \"users\" (\n\t\"id\" integer NOT NULL\n);
+
+            -- @materialized view
+            CREATE TABLE \"user_stats\" (\n\t\"user_id\" integer\n);
+            ",
+        )
+        .unwrap();
+        assert!(
+            live_database
+                .find_table("user_stats")
+                .unwrap()
+                .is_materialized_view
+        );
+        assert!(DatabaseDiff::new(&dialect, &live_database, &database).is_empty());
+        assert!(DatabaseDiff::new(&dialect, &database, &live_database).is_empty());
+    }
+
+    #[test]
+    fn compare_type_aliases_by_name() {
+        let custom_type = |name: &str| DataType::Custom(ObjectName(vec![Ident::new(name)]));
+        let create_database = |columns: Vec<Column>| {
+            Database::new(
+                "public".to_string(),
+                vec![Table::new("users".to_string(), columns)],
+            )
+        };
+        let database = create_database(vec![
+            create_column("is_active", custom_type("bool"), true),
+            create_column("id", custom_type("int8"), true),
+            create_column("score", custom_type("float8"), false),
+            create_column("name", DataType::Varchar(Some(50)), false),
+        ]);
+        let live_database = create_database(vec![
+            create_column("is_active", DataType::Boolean, true),
+            create_column("id", DataType::BigInt(None), true),
+            create_column("score", DataType::Double, false),
+            create_column("name", DataType::Varchar(Some(100)), false),
+        ]);
+        let diff = DatabaseDiff::new(&PostgresDialect::new(), &database, &live_database);
+        assert_eq!(
+            diff.to_string(),
+            "~ column \"users\".\"name\" type CHARACTER VARYING(50) -> CHARACTER VARYING(100)\n"
+        );
+    }
+
+    #[test]
+    fn flag_removed_enum_values() {
+        let dialect = PostgresDialect::new();
+        let old_database =
+            Database::from_schema_ddl(&dialect, "CREATE TYPE role AS ENUM ('admin', 'guest');")
+                .unwrap();
+        let new_database =
+            Database::from_schema_ddl(&dialect, "CREATE TYPE role AS ENUM ('admin', 'member');")
+                .unwrap();
+        let diff = DatabaseDiff::new(&dialect, &old_database, &new_database);
+        assert!(diff.is_destructive());
+        assert_eq!(
+            diff.to_ddl(),
+            "\
+            -- WARNING: Destructive change, values can't be removed from the enum type, the type has to be recreated.\n\
+            ALTER TYPE \"role\" ADD VALUE 'member';\n\
+            -- Recreate the type without the 'guest' values.\n"
+        );
+    }
+}
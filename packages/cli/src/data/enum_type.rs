@@ -0,0 +1,11 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumType {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl EnumType {
+    pub fn new(name: String, values: Vec<String>) -> EnumType {
+        EnumType { name, values }
+    }
+}
@@ -0,0 +1,81 @@
+use super::{Column, Table};
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    // Unnamed arguments have an empty name and the ones with the default value
+    // can be omitted.
+    pub arguments: Vec<Column>,
+    // Columns of the returned rows, the scalar result is a single column
+    // named after the function.
+    pub result_table: Table,
+    pub returns_set: bool,
+}
+
+impl Clone for Function {
+    fn clone(&self) -> Function {
+        Function {
+            name: self.name.clone(),
+            arguments: self.arguments.clone(),
+            result_table: self.result_table.clone(),
+            returns_set: self.returns_set,
+        }
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        self.name == other.name
+            && self.arguments == other.arguments
+            && self.result_table == other.result_table
+            && self.returns_set == other.returns_set
+    }
+}
+
+impl Function {
+    pub fn new(
+        name: String,
+        arguments: Vec<Column>,
+        result_columns: Vec<Column>,
+        returns_set: bool,
+    ) -> Function {
+        Function {
+            result_table: Table::new(name.clone(), result_columns),
+            name,
+            arguments,
+            returns_set,
+        }
+    }
+
+    // Overloaded functions are told apart by the number of the arguments.
+    pub fn accepts_argument_count(&self, argument_count: usize) -> bool {
+        let required_argument_count = self
+            .arguments
+            .iter()
+            .filter(|argument| argument.default_value.is_none())
+            .count();
+        (required_argument_count..=self.arguments.len()).contains(&argument_count)
+    }
+
+    // Whether the function has the signature, like the one of `DROP FUNCTION`.
+    pub fn has_argument_types(&self, arguments: &[Column]) -> bool {
+        self.arguments.len() == arguments.len()
+            && self
+                .arguments
+                .iter()
+                .zip(arguments)
+                .all(|(argument, other_argument)| {
+                    argument.sql_type == other_argument.sql_type
+                        && argument.array_dimensions == other_argument.array_dimensions
+                })
+    }
+
+    // Only the functions returning a single column can be called in the
+    // select list.
+    pub fn get_scalar_result_column(&self) -> Option<&Column> {
+        match &self.result_table.columns[..] {
+            [column] => Some(column),
+            _ => None,
+        }
+    }
+}
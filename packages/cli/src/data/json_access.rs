@@ -0,0 +1,19 @@
+// Access to the nested value of the JSON column with the `->` and `->>`
+// operators, like `settings -> 'theme' ->> 'color'`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonAccess {
+    pub path: Vec<String>,
+    // The `->>` operator returns the value as text.
+    pub is_text: bool,
+}
+
+impl JsonAccess {
+    // The SQL parser doesn't support the JSON operators, so the queries call
+    // these functions instead.
+    pub const FUNCTION_NAME: &'static str = "_sql_codegen_json_access";
+    pub const TEXT_FUNCTION_NAME: &'static str = "_sql_codegen_json_access_text";
+
+    pub fn new(path: Vec<String>, is_text: bool) -> JsonAccess {
+        JsonAccess { path, is_text }
+    }
+}
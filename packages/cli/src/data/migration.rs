@@ -0,0 +1,73 @@
+use crate::{error, utils};
+use std::fs;
+use std::path::PathBuf;
+
+const UP_MARKER: &str = "-- migrate:up";
+const DOWN_MARKER: &str = "-- migrate:down";
+
+// Migration file with the optional `-- migrate:up` and `-- migrate:down`
+// sections. The file without the markers is applied as a whole.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Migration {
+    pub name: String,
+    pub up: String,
+    pub down: Option<String>,
+    pub checksum: String,
+}
+
+impl Migration {
+    pub fn new(name: String, content: &str) -> Migration {
+        let mut up = String::new();
+        let mut down: Option<String> = None;
+        for line in content.lines() {
+            match line.trim() {
+                UP_MARKER => {}
+                DOWN_MARKER => down = Some(String::new()),
+                _ => {
+                    let section = down.as_mut().unwrap_or(&mut up);
+                    section.push_str(line);
+                    section.push('\n');
+                }
+            }
+        }
+        Migration {
+            name,
+            up,
+            down: down.filter(|down| !down.trim().is_empty()),
+            checksum: utils::checksum(content),
+        }
+    }
+
+    pub fn from_file_path(file_path: &PathBuf) -> Result<Migration, error::CodegenError> {
+        let content = fs::read_to_string(file_path)?;
+        let name = file_path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        Ok(Migration::new(name, &content))
+    }
+
+    pub fn to_file_content(up: &str, down: &str) -> String {
+        format!("{UP_MARKER}\n{up}\n{DOWN_MARKER}\n{down}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_sections() {
+        let migration = Migration::new(
+            "1_users.sql".to_string(),
+            "-- migrate:up\nCREATE TABLE users (id INT);\n-- migrate:down\nDROP TABLE users;\n",
+        );
+        assert_eq!(migration.up, "CREATE TABLE users (id INT);\n");
+        assert_eq!(migration.down, Some("DROP TABLE users;\n".to_string()));
+
+        let migration = Migration::new("2_posts.sql".to_string(), "CREATE TABLE posts (id INT);");
+        assert_eq!(migration.up, "CREATE TABLE posts (id INT);\n");
+        assert_eq!(migration.down, None);
+    }
+}
@@ -1,9 +1,12 @@
 use super::Selection;
-use crate::{data, error};
-use sqlparser::ast::{Expr, Ident, SelectItem, TableFactor, TableWithJoins};
+use crate::{data, error, utils};
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, Ident, SelectItem, TableFactor, TableWithJoins, Value,
+};
 
 #[derive(Debug)]
 pub struct Projection<'a> {
+    pub database: &'a data::Database,
     pub selections: Vec<data::Selection<'a>>,
 }
 
@@ -15,8 +18,22 @@ impl<'a> Projection<'a> {
         let selections_of_selections = tables_with_joins
             .iter()
             .map(|table_with_joins| match &table_with_joins.relation {
-                TableFactor::Table { name, alias, .. } => {
-                    match database.find_table(&name.to_string()) {
+                TableFactor::Table {
+                    name, alias, args, ..
+                } => {
+                    // Set returning functions are queried like the tables.
+                    let table = if args.is_empty() {
+                        database.find_table(&name.to_string())
+                    } else {
+                        None
+                    };
+                    let table = match table {
+                        Some(table) => Some(table),
+                        None => database
+                            .find_function(&utils::object_name_to_string(name), args.len())?
+                            .map(|function| &function.result_table),
+                    };
+                    match table {
                         Some(table) => {
                             let selections = table
                                 .columns
@@ -50,7 +67,90 @@ impl<'a> Projection<'a> {
             .flatten()
             .collect::<Vec<Selection>>();
 
-        Ok(Projection { selections })
+        Ok(Projection {
+            database,
+            selections,
+        })
+    }
+
+    fn filter_by_function(
+        &self,
+        function: &Function,
+        alias: Option<&Ident>,
+    ) -> Result<Vec<Selection<'a>>, error::CodegenError> {
+        let function_name = utils::object_name_to_string(&function.name);
+        if function_name == data::JsonAccess::FUNCTION_NAME
+            || function_name == data::JsonAccess::TEXT_FUNCTION_NAME
+        {
+            return self.filter_by_json_access(function, alias);
+        }
+        let database_function = self
+            .database
+            .find_function(&function_name, function.args.len())?
+            .ok_or_else(|| {
+                error::CodegenError::QueryError(format!("Function \"{function_name}\" not found"))
+            })?;
+        let column = database_function
+            .get_scalar_result_column()
+            .ok_or_else(|| {
+                error::CodegenError::QueryError(format!(
+                    "Function \"{function_name}\" returning multiple columns has to be called in the FROM clause"
+                ))
+            })?;
+        // The result column is named after the function by default.
+        Ok(vec![Selection::new(
+            self.database,
+            function_name.clone(),
+            &database_function.result_table,
+            match alias {
+                Some(alias) => alias.value.clone(),
+                None => function_name,
+            },
+            column,
+        )])
+    }
+
+    // The JSON operators are rewritten to the function calls before parsing.
+    fn filter_by_json_access(
+        &self,
+        function: &Function,
+        alias: Option<&Ident>,
+    ) -> Result<Vec<Selection<'a>>, error::CodegenError> {
+        let mut args = function.args.iter().map(|arg| match arg {
+            FunctionArg::Named { arg, .. } => arg,
+            FunctionArg::Unnamed(arg) => arg,
+        });
+        let mut selections = match args.next() {
+            Some(Expr::CompoundIdentifier(identifiers)) => {
+                self.filter_by_compound_identifier(identifiers, None)?
+            }
+            Some(Expr::Identifier(identifier)) => self.filter_by_identifier(identifier, None)?,
+            _ => {
+                return Err(error::CodegenError::QueryError(
+                    "The JSON operators are only supported on columns".to_string(),
+                ))
+            }
+        };
+        let path = args
+            .map(|arg| match arg {
+                Expr::Value(Value::SingleQuotedString(key)) => Ok(key.clone()),
+                Expr::Value(Value::Number(index, _)) => Ok(index.to_string()),
+                _ => Err(error::CodegenError::QueryError(format!(
+                    "The \"{arg}\" JSON path element is not supported"
+                ))),
+            })
+            .collect::<Result<Vec<String>, error::CodegenError>>()?;
+        let is_text =
+            utils::object_name_to_string(&function.name) == data::JsonAccess::TEXT_FUNCTION_NAME;
+        for selection in &mut selections {
+            selection.json_access = Some(data::JsonAccess::new(path.clone(), is_text));
+            // Postgres doesn't name the expression columns.
+            selection.column_name = match alias {
+                Some(alias) => alias.value.clone(),
+                None => "?column?".to_string(),
+            };
+        }
+        Ok(selections)
     }
 
     fn filter_by_compound_identifier(
@@ -139,6 +239,7 @@ impl<'a> Projection<'a> {
                         self.filter_by_compound_identifier(identifiers, None)
                     }
                     Expr::Identifier(identifier) => self.filter_by_identifier(identifier, None),
+                    Expr::Function(function) => self.filter_by_function(function, None),
                     _ => Err(error::CodegenError::QueryError(format!(
                         "Not supported expression"
                     ))),
@@ -150,6 +251,7 @@ impl<'a> Projection<'a> {
                     Expr::Identifier(identifier) => {
                         self.filter_by_identifier(identifier, Some(alias))
                     }
+                    Expr::Function(function) => self.filter_by_function(function, Some(alias)),
                     _ => Err(error::CodegenError::QueryError(format!(
                         "Not supported expression"
                     ))),
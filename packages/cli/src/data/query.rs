@@ -1,6 +1,7 @@
+use crate::dialects::Dialect;
+use crate::lexer::{self, Token, TokenKind};
 use crate::{data, error};
 use sqlparser::ast::{SetExpr, Statement};
-use sqlparser::dialect::PostgreSqlDialect;
 use sqlparser::parser::Parser;
 use std::fs;
 use std::path::PathBuf;
@@ -22,14 +23,17 @@ impl<'a> Query<'a> {
     }
 
     pub fn from_query_file_paths(
+        dialect: &dyn Dialect,
         database: &'a data::Database,
         query_file_paths: Vec<PathBuf>,
     ) -> Result<Vec<Query<'a>>, error::CodegenError> {
-        let dialect = PostgreSqlDialect {};
         let mut queries: Vec<Query> = vec![];
         for query_file_path in query_file_paths {
             let query_ddl = fs::read_to_string(&query_file_path)?;
-            let query_ast = Parser::parse_sql(&dialect, &query_ddl)?;
+            let query_ast = Parser::parse_sql(
+                dialect.get_parser_dialect(),
+                &to_parsable_sql(dialect, &query_ddl)?,
+            )?;
             queries.push(Query::from_ast(
                 database,
                 query_file_path,
@@ -62,3 +66,101 @@ impl<'a> Query<'a> {
         )))
     }
 }
+
+// The SQL parser doesn't support the placeholders, like `$1`, so they are
+// replaced with NULL, which fits in any expression.
+fn to_parsable_sql(dialect: &dyn Dialect, query_ddl: &str) -> Result<String, error::CodegenError> {
+    let tokens = dialect.tokenize(query_ddl)?;
+    let mut sql = String::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        if let Some((json_access_sql, next_index)) = to_json_access_sql(&tokens, index) {
+            sql.push_str(&json_access_sql);
+            index = next_index;
+            continue;
+        }
+        sql.push_str(if dialect.is_placeholder(&tokens[index]) {
+            "NULL"
+        } else {
+            tokens[index].text.as_str()
+        });
+        index += 1;
+    }
+    Ok(sql)
+}
+
+// The SQL parser doesn't support the JSON operators either, so the access
+// paths, like `settings -> 'theme' ->> 'color'`, are turned into the function
+// calls, like `_sql_codegen_json_access_text(settings, 'theme', 'color')`.
+fn to_json_access_sql(tokens: &[Token], start: usize) -> Option<(String, usize)> {
+    let is_name =
+        |token: &Token| token.kind == TokenKind::Word || token.kind == TokenKind::QuotedIdentifier;
+    let next_index = |index: usize| (index..tokens.len()).find(|index| !tokens[*index].is_trivia());
+    // The column can be qualified with the table name, like `users.settings`.
+    if !is_name(&tokens[start]) || (start > 0 && tokens[start - 1].is_punctuation(".")) {
+        return None;
+    }
+    let mut operand_end = start + 1;
+    while operand_end + 1 < tokens.len()
+        && tokens[operand_end].is_punctuation(".")
+        && is_name(&tokens[operand_end + 1])
+    {
+        operand_end += 2;
+    }
+
+    let mut keys = vec![];
+    let mut is_text = false;
+    let mut end = operand_end;
+    while let Some(operator_index) = next_index(end) {
+        let operator = &tokens[operator_index];
+        if is_text || !(operator.is_punctuation("->") || operator.is_punctuation("->>")) {
+            break;
+        }
+        let key_index = match next_index(operator_index + 1) {
+            Some(key_index) => key_index,
+            None => break,
+        };
+        let key = &tokens[key_index];
+        if key.kind != TokenKind::String && key.kind != TokenKind::Number {
+            break;
+        }
+        keys.push(key.text.clone());
+        is_text = operator.is_punctuation("->>");
+        end = key_index + 1;
+    }
+    if keys.is_empty() {
+        return None;
+    }
+    let function_name = if is_text {
+        data::JsonAccess::TEXT_FUNCTION_NAME
+    } else {
+        data::JsonAccess::FUNCTION_NAME
+    };
+    Some((
+        format!(
+            "{function_name}({}, {})",
+            lexer::to_sql(&tokens[start..operand_end]),
+            keys.join(", ")
+        ),
+        end,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::PostgresDialect;
+
+    #[test]
+    fn rewrite_json_access() {
+        assert_eq!(
+            to_parsable_sql(&PostgresDialect::new(), "SELECT u.settings -> 'theme' ->> 'color' AS color FROM users u WHERE id = $1")
+                .unwrap(),
+            "SELECT _sql_codegen_json_access_text(u.settings, 'theme', 'color') AS color FROM users u WHERE id = NULL"
+        );
+        assert_eq!(
+            to_parsable_sql(&PostgresDialect::new(), "SELECT tags -> 0 FROM posts").unwrap(),
+            "SELECT _sql_codegen_json_access(tags, 0) FROM posts"
+        );
+    }
+}
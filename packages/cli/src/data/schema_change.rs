@@ -0,0 +1,783 @@
+use super::{Column, ColumnIdentity, EnumType, Function, Table, TableDefinition};
+use crate::dialects::Dialect;
+use crate::error;
+use crate::lexer::{self, Cursor, Token};
+
+const FUNCTION_ATTRIBUTE_KEYWORDS: [&str; 21] = [
+    "LANGUAGE",
+    "AS",
+    "STRICT",
+    "IMMUTABLE",
+    "STABLE",
+    "VOLATILE",
+    "CALLED",
+    "RETURNS",
+    "SECURITY",
+    "EXTERNAL",
+    "PARALLEL",
+    "COST",
+    "ROWS",
+    "SUPPORT",
+    "SET",
+    "WINDOW",
+    "LEAKPROOF",
+    "NOT",
+    "BEGIN",
+    "RETURN",
+    "TRANSFORM",
+];
+
+#[derive(Debug)]
+pub enum AlterTableAction {
+    AddColumn {
+        column: Column,
+        if_not_exists: bool,
+    },
+    DropColumn {
+        column_name: String,
+        if_exists: bool,
+    },
+    RenameColumn {
+        column_name: String,
+        new_column_name: String,
+    },
+    RenameTable {
+        new_table_name: String,
+    },
+    // The column holds the new type of the altered column.
+    AlterColumnType {
+        column_name: String,
+        column: Column,
+    },
+    SetNotNull {
+        column_name: String,
+    },
+    DropNotNull {
+        column_name: String,
+    },
+    SetDefault {
+        column_name: String,
+        default_value: String,
+    },
+    DropDefault {
+        column_name: String,
+    },
+    SetIdentity {
+        column_name: String,
+        identity: ColumnIdentity,
+    },
+    AddPrimaryKey {
+        column_names: Vec<String>,
+    },
+    AddUnique {
+        column_names: Vec<String>,
+    },
+    AddCheck {
+        column_name: String,
+        allowed_values: Vec<String>,
+    },
+    // The partition table is folded under its parent table.
+    AttachPartition {
+        partition_name: String,
+    },
+}
+
+#[derive(Debug)]
+pub enum SchemaChange {
+    CreateTable {
+        table: Table,
+        if_not_exists: bool,
+        inherited_table_names: Vec<String>,
+    },
+    AlterTable {
+        table_name: String,
+        if_exists: bool,
+        actions: Vec<AlterTableAction>,
+    },
+    DropTable {
+        table_names: Vec<String>,
+        if_exists: bool,
+    },
+    CreateIndex {
+        table_name: String,
+        column_names: Vec<String>,
+        is_unique: bool,
+    },
+    CreateType {
+        enum_type: EnumType,
+    },
+    // The `None` description removes the comment.
+    CommentOnTable {
+        table_name: String,
+        description: Option<String>,
+    },
+    CommentOnColumn {
+        table_name: String,
+        column_name: String,
+        description: Option<String>,
+    },
+    CreateFunction {
+        function: Function,
+    },
+    // The functions without the argument types drop all the overloads.
+    DropFunction {
+        functions: Vec<(String, Option<Vec<Column>>)>,
+        if_exists: bool,
+    },
+}
+
+impl SchemaChange {
+    // Returns `None` for the statements that don't change the schema.
+    pub fn from_tokens(
+        dialect: &dyn Dialect,
+        tokens: &[Token],
+    ) -> Result<Option<SchemaChange>, error::CodegenError> {
+        let mut cursor = Cursor::new(tokens);
+        if TableDefinition::is_create_table(tokens) {
+            cursor.parse_keyword("CREATE");
+            if !cursor.parse_keyword("FOREIGN") {
+                cursor.parse_keyword("UNLOGGED");
+            }
+            cursor.parse_keyword("TABLE");
+            let if_not_exists = cursor.parse_keywords(&["IF", "NOT", "EXISTS"]);
+            let table_name = cursor.parse_object_name();
+            // Partitions are folded under their parent table, like the
+            // attached ones.
+            if cursor.parse_keywords(&["PARTITION", "OF"]) {
+                return match (table_name, cursor.parse_object_name()) {
+                    (Some(partition_name), Some(parent_table_name)) => {
+                        Ok(Some(SchemaChange::AlterTable {
+                            table_name: parent_table_name,
+                            if_exists: false,
+                            actions: vec![AlterTableAction::AttachPartition { partition_name }],
+                        }))
+                    }
+                    _ => Err(invalid_statement(tokens)),
+                };
+            }
+            let table_definition = TableDefinition::from_tokens(dialect, tokens)?;
+            let table = Table::from_table_definition(&table_definition)?;
+            return Ok(Some(SchemaChange::CreateTable {
+                table,
+                if_not_exists,
+                inherited_table_names: table_definition.inherited_table_names,
+            }));
+        }
+        if cursor.parse_keywords(&["ALTER", "TABLE"]) {
+            return SchemaChange::parse_alter_table(dialect, cursor, tokens).map(Some);
+        }
+        if cursor.parse_keywords(&["DROP", "TABLE"]) {
+            return SchemaChange::parse_drop_table(cursor, tokens).map(Some);
+        }
+        if cursor.parse_keywords(&["CREATE", "TYPE"]) {
+            return SchemaChange::parse_create_type(cursor, tokens);
+        }
+        if cursor.parse_keywords(&["CREATE", "FUNCTION"])
+            || cursor.parse_keywords(&["CREATE", "OR", "REPLACE", "FUNCTION"])
+        {
+            return SchemaChange::parse_create_function(dialect, cursor, tokens).map(Some);
+        }
+        if cursor.parse_keywords(&["DROP", "FUNCTION"]) {
+            return SchemaChange::parse_drop_function(dialect, cursor, tokens).map(Some);
+        }
+        if cursor.parse_keywords(&["COMMENT", "ON"]) {
+            return SchemaChange::parse_comment(cursor, tokens);
+        }
+        let is_unique = cursor.parse_keywords(&["CREATE", "UNIQUE", "INDEX"]);
+        if is_unique || cursor.parse_keywords(&["CREATE", "INDEX"]) {
+            return SchemaChange::parse_create_index(cursor, tokens, is_unique).map(Some);
+        }
+        Ok(None)
+    }
+
+    fn parse_alter_table(
+        dialect: &dyn Dialect,
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<SchemaChange, error::CodegenError> {
+        let if_exists = cursor.parse_keywords(&["IF", "EXISTS"]);
+        cursor.parse_keyword("ONLY");
+        let table_name = cursor
+            .parse_object_name()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        let action_tokens = cursor.rest();
+        let mut actions = vec![];
+        for (start, end) in lexer::split_by_commas(&action_tokens) {
+            if let Some(action) =
+                SchemaChange::parse_alter_table_action(dialect, &action_tokens[start..end], tokens)?
+            {
+                actions.push(action);
+            }
+        }
+        Ok(SchemaChange::AlterTable {
+            table_name,
+            if_exists,
+            actions,
+        })
+    }
+
+    fn parse_alter_table_action(
+        dialect: &dyn Dialect,
+        action_tokens: &[Token],
+        tokens: &[Token],
+    ) -> Result<Option<AlterTableAction>, error::CodegenError> {
+        let mut cursor = Cursor::new(action_tokens);
+        if cursor.parse_keyword("ADD") {
+            if cursor.parse_keyword("CONSTRAINT") {
+                cursor.parse_identifier();
+            }
+            if cursor.parse_keywords(&["PRIMARY", "KEY"]) {
+                let column_names = parse_column_names(&mut cursor, tokens)?;
+                return Ok(Some(AlterTableAction::AddPrimaryKey { column_names }));
+            }
+            if cursor.parse_keyword("UNIQUE") {
+                cursor.parse_keywords(&["NULLS", "NOT", "DISTINCT"]);
+                cursor.parse_keywords(&["NULLS", "DISTINCT"]);
+                let column_names = parse_column_names(&mut cursor, tokens)?;
+                return Ok(Some(AlterTableAction::AddUnique { column_names }));
+            }
+            if cursor.parse_keyword("CHECK") {
+                // Only the checks limiting the column to the literal set matter.
+                let allowed_values = cursor
+                    .parse_parenthesized()
+                    .and_then(|expression_tokens| {
+                        TableDefinition::parse_allowed_values(&expression_tokens)
+                    })
+                    .map(|(column_name, allowed_values)| AlterTableAction::AddCheck {
+                        column_name,
+                        allowed_values,
+                    });
+                return Ok(allowed_values);
+            }
+            let is_constraint = ["FOREIGN", "EXCLUDE"]
+                .iter()
+                .any(|keyword| matches!(cursor.peek(), Some(token) if token.is_keyword(keyword)));
+            if is_constraint {
+                return Ok(None);
+            }
+            cursor.parse_keyword("COLUMN");
+            let if_not_exists = cursor.parse_keywords(&["IF", "NOT", "EXISTS"]);
+            let column = parse_column_definition(dialect, &cursor.rest_to_sql())?;
+            return Ok(Some(AlterTableAction::AddColumn {
+                column,
+                if_not_exists,
+            }));
+        }
+        if cursor.parse_keywords(&["ATTACH", "PARTITION"]) {
+            let partition_name = cursor
+                .parse_object_name()
+                .ok_or_else(|| invalid_statement(tokens))?;
+            return Ok(Some(AlterTableAction::AttachPartition { partition_name }));
+        }
+        if cursor.parse_keyword("DROP") {
+            if cursor.parse_keyword("CONSTRAINT") {
+                return Ok(None);
+            }
+            cursor.parse_keyword("COLUMN");
+            let if_exists = cursor.parse_keywords(&["IF", "EXISTS"]);
+            let column_name = cursor
+                .parse_identifier()
+                .ok_or_else(|| invalid_statement(tokens))?;
+            return Ok(Some(AlterTableAction::DropColumn {
+                column_name,
+                if_exists,
+            }));
+        }
+        if cursor.parse_keyword("RENAME") {
+            if cursor.parse_keyword("TO") {
+                let new_table_name = cursor
+                    .parse_object_name()
+                    .ok_or_else(|| invalid_statement(tokens))?;
+                return Ok(Some(AlterTableAction::RenameTable { new_table_name }));
+            }
+            if cursor.parse_keyword("CONSTRAINT") {
+                return Ok(None);
+            }
+            cursor.parse_keyword("COLUMN");
+            let column_name = cursor.parse_identifier();
+            let has_to = cursor.parse_keyword("TO");
+            let new_column_name = cursor.parse_identifier();
+            return match (column_name, has_to, new_column_name) {
+                (Some(column_name), true, Some(new_column_name)) => {
+                    Ok(Some(AlterTableAction::RenameColumn {
+                        column_name,
+                        new_column_name,
+                    }))
+                }
+                _ => Err(invalid_statement(tokens)),
+            };
+        }
+        if cursor.parse_keyword("ALTER") {
+            cursor.parse_keyword("COLUMN");
+            let column_name = cursor
+                .parse_identifier()
+                .ok_or_else(|| invalid_statement(tokens))?;
+            if cursor.parse_keywords(&["SET", "DATA", "TYPE"]) || cursor.parse_keyword("TYPE") {
+                // The USING expression only matters for converting the data.
+                let type_tokens = cursor
+                    .rest()
+                    .into_iter()
+                    .take_while(|token| !token.is_keyword("USING"))
+                    .collect::<Vec<Token>>();
+                let type_ddl = lexer::to_sql(&type_tokens);
+                let column = parse_column_definition(
+                    dialect,
+                    &format!("{} {type_ddl}", dialect.quote_identifier(&column_name)),
+                )?;
+                return Ok(Some(AlterTableAction::AlterColumnType {
+                    column_name,
+                    column,
+                }));
+            }
+            if cursor.parse_keywords(&["SET", "NOT", "NULL"]) {
+                return Ok(Some(AlterTableAction::SetNotNull { column_name }));
+            }
+            if cursor.parse_keywords(&["DROP", "NOT", "NULL"]) {
+                return Ok(Some(AlterTableAction::DropNotNull { column_name }));
+            }
+            if cursor.parse_keywords(&["SET", "DEFAULT"]) {
+                return Ok(Some(AlterTableAction::SetDefault {
+                    column_name,
+                    default_value: cursor.rest_to_sql(),
+                }));
+            }
+            if cursor.parse_keywords(&["DROP", "DEFAULT"]) {
+                return Ok(Some(AlterTableAction::DropDefault { column_name }));
+            }
+            // The pg_dump adds identity to the columns after creating the table.
+            if cursor.parse_keywords(&["ADD", "GENERATED", "ALWAYS", "AS", "IDENTITY"]) {
+                return Ok(Some(AlterTableAction::SetIdentity {
+                    column_name,
+                    identity: ColumnIdentity::Always,
+                }));
+            }
+            if cursor.parse_keywords(&["ADD", "GENERATED", "BY", "DEFAULT", "AS", "IDENTITY"]) {
+                return Ok(Some(AlterTableAction::SetIdentity {
+                    column_name,
+                    identity: ColumnIdentity::ByDefault,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn parse_drop_table(
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<SchemaChange, error::CodegenError> {
+        let if_exists = cursor.parse_keywords(&["IF", "EXISTS"]);
+        let mut table_names = vec![];
+        loop {
+            let table_name = cursor
+                .parse_object_name()
+                .ok_or_else(|| invalid_statement(tokens))?;
+            table_names.push(table_name);
+            if !cursor.parse_punctuation(",") {
+                break;
+            }
+        }
+        Ok(SchemaChange::DropTable {
+            table_names,
+            if_exists,
+        })
+    }
+
+    fn parse_create_type(
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<Option<SchemaChange>, error::CodegenError> {
+        let name = cursor
+            .parse_object_name()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        // Only the enum types are supported, composite and range types are skipped.
+        if !cursor.parse_keywords(&["AS", "ENUM"]) {
+            return Ok(None);
+        }
+        let value_tokens = cursor
+            .parse_parenthesized()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        let values = value_tokens
+            .iter()
+            .filter_map(|token| token.string_value())
+            .collect::<Vec<String>>();
+        Ok(Some(SchemaChange::CreateType {
+            enum_type: EnumType::new(name, values),
+        }))
+    }
+
+    fn parse_create_function(
+        dialect: &dyn Dialect,
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<SchemaChange, error::CodegenError> {
+        let name = cursor
+            .parse_object_name()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        let argument_tokens = cursor
+            .parse_parenthesized()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        let (arguments, output_columns) = parse_function_arguments(dialect, &argument_tokens)?;
+
+        // Functions with the output arguments can skip the return type.
+        let mut returns_set = false;
+        let mut result_columns = output_columns.clone();
+        if cursor.parse_keyword("RETURNS") {
+            returns_set = cursor.parse_keyword("SETOF");
+            if cursor.parse_keyword("TABLE") {
+                returns_set = true;
+                let column_tokens = cursor
+                    .parse_parenthesized()
+                    .ok_or_else(|| invalid_statement(tokens))?;
+                let ddl = format!("CREATE TABLE \"_\" ({})", lexer::to_sql(&column_tokens));
+                let table_definition =
+                    TableDefinition::from_tokens(dialect, &dialect.tokenize(&ddl)?)?;
+                result_columns = Table::from_table_definition(&table_definition)?.columns;
+            } else {
+                let mut type_tokens = vec![];
+                while let Some(token) = cursor.peek() {
+                    if FUNCTION_ATTRIBUTE_KEYWORDS
+                        .iter()
+                        .any(|keyword| token.is_keyword(keyword))
+                    {
+                        break;
+                    }
+                    type_tokens.push(token.text.as_str());
+                    cursor.advance();
+                }
+                let is_record = matches!(&type_tokens[..], [type_name] if type_name.eq_ignore_ascii_case("RECORD"));
+                if !is_record || output_columns.is_empty() {
+                    let column = parse_column_definition(
+                        dialect,
+                        &format!(
+                            "{} {}",
+                            dialect.quote_identifier(&name),
+                            type_tokens.join(" ")
+                        ),
+                    )?;
+                    result_columns = vec![column];
+                }
+            }
+        }
+        Ok(SchemaChange::CreateFunction {
+            function: Function::new(name, arguments, result_columns, returns_set),
+        })
+    }
+
+    fn parse_drop_function(
+        dialect: &dyn Dialect,
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<SchemaChange, error::CodegenError> {
+        let if_exists = cursor.parse_keywords(&["IF", "EXISTS"]);
+        let mut functions = vec![];
+        loop {
+            let function_name = cursor
+                .parse_object_name()
+                .ok_or_else(|| invalid_statement(tokens))?;
+            // The argument types tell the overloaded functions apart.
+            let arguments = match cursor.parse_parenthesized() {
+                Some(argument_tokens) => {
+                    Some(parse_function_arguments(dialect, &argument_tokens)?.0)
+                }
+                None => None,
+            };
+            functions.push((function_name, arguments));
+            if !cursor.parse_punctuation(",") {
+                break;
+            }
+        }
+        Ok(SchemaChange::DropFunction {
+            functions,
+            if_exists,
+        })
+    }
+
+    fn parse_comment(
+        mut cursor: Cursor,
+        tokens: &[Token],
+    ) -> Result<Option<SchemaChange>, error::CodegenError> {
+        // Comments on the other objects, like extensions, are skipped.
+        let is_table = cursor.parse_keyword("TABLE");
+        if !is_table && !cursor.parse_keyword("COLUMN") {
+            return Ok(None);
+        }
+        let mut names = cursor
+            .parse_qualified_name()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        if !cursor.parse_keyword("IS") {
+            return Err(invalid_statement(tokens));
+        }
+        let description = if cursor.parse_keyword("NULL") {
+            None
+        } else {
+            let description = cursor
+                .advance()
+                .and_then(|token| token.string_value())
+                .ok_or_else(|| invalid_statement(tokens))?;
+            Some(description)
+        };
+        if is_table {
+            let table_name = names.pop().ok_or_else(|| invalid_statement(tokens))?;
+            return Ok(Some(SchemaChange::CommentOnTable {
+                table_name,
+                description,
+            }));
+        }
+        match (names.pop(), names.pop()) {
+            (Some(column_name), Some(table_name)) => Ok(Some(SchemaChange::CommentOnColumn {
+                table_name,
+                column_name,
+                description,
+            })),
+            _ => Err(invalid_statement(tokens)),
+        }
+    }
+
+    fn parse_create_index(
+        mut cursor: Cursor,
+        tokens: &[Token],
+        is_unique: bool,
+    ) -> Result<SchemaChange, error::CodegenError> {
+        cursor.parse_keyword("CONCURRENTLY");
+        cursor.parse_keywords(&["IF", "NOT", "EXISTS"]);
+        if !cursor.parse_keyword("ON") {
+            cursor.parse_object_name();
+            if !cursor.parse_keyword("ON") {
+                return Err(invalid_statement(tokens));
+            }
+        }
+        cursor.parse_keyword("ONLY");
+        let table_name = cursor
+            .parse_object_name()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        if cursor.parse_keyword("USING") {
+            cursor.advance();
+        }
+        let column_tokens = cursor
+            .parse_parenthesized()
+            .ok_or_else(|| invalid_statement(tokens))?;
+        let column_names = lexer::split_by_commas(&column_tokens)
+            .into_iter()
+            .map(|(start, end)| {
+                // Expressions are not tied to a single column.
+                let mut cursor = Cursor::new(&column_tokens[start..end]);
+                let column_name = cursor.parse_identifier();
+                match cursor.peek() {
+                    Some(token) if token.is_punctuation("(") || token.is_punctuation(".") => None,
+                    _ => column_name,
+                }
+            })
+            .collect::<Option<Vec<String>>>()
+            .unwrap_or_default();
+        // A partial index doesn't make the column unique.
+        let is_partial = cursor.rest().iter().any(|token| token.is_keyword("WHERE"));
+        Ok(SchemaChange::CreateIndex {
+            table_name,
+            column_names,
+            is_unique: is_unique && !is_partial,
+        })
+    }
+}
+
+fn parse_column_definition(
+    dialect: &dyn Dialect,
+    column_ddl: &str,
+) -> Result<Column, error::CodegenError> {
+    let ddl = format!(
+        "CREATE TABLE {} ({column_ddl})",
+        dialect.quote_identifier("_")
+    );
+    let table_definition = TableDefinition::from_tokens(dialect, &dialect.tokenize(&ddl)?)?;
+    let table = Table::from_table_definition(&table_definition)?;
+    table.columns.into_iter().next().ok_or_else(|| {
+        error::CodegenError::SchemaError(format!("Invalid column definition: {column_ddl}"))
+    })
+}
+
+// Returns the input arguments and the output columns of the function.
+fn parse_function_arguments(
+    dialect: &dyn Dialect,
+    argument_tokens: &[Token],
+) -> Result<(Vec<Column>, Vec<Column>), error::CodegenError> {
+    let mut arguments = vec![];
+    let mut output_columns = vec![];
+    for (start, end) in lexer::split_by_commas(argument_tokens) {
+        let mut argument_cursor = Cursor::new(&argument_tokens[start..end]);
+        let is_output = argument_cursor.parse_keyword("OUT");
+        let is_input_output = !is_output && argument_cursor.parse_keyword("INOUT");
+        if !is_output && !is_input_output && !argument_cursor.parse_keyword("IN") {
+            argument_cursor.parse_keyword("VARIADIC");
+        }
+        // The default value doesn't change the type of the argument, but
+        // makes the argument optional.
+        let argument_tokens = argument_cursor.rest();
+        let default_index = argument_tokens
+            .iter()
+            .position(|token| token.is_keyword("DEFAULT") || token.is_punctuation("="));
+        let argument_ddl =
+            lexer::to_sql(&argument_tokens[..default_index.unwrap_or(argument_tokens.len())]);
+        let mut argument = parse_function_argument(dialect, &argument_ddl)?;
+        argument.default_value = default_index.map(|index| {
+            lexer::to_sql(&argument_tokens[index + 1..])
+                .trim()
+                .to_string()
+        });
+        if is_output || is_input_output {
+            output_columns.push(argument.clone());
+        }
+        if !is_output {
+            arguments.push(argument);
+        }
+    }
+    Ok((arguments, output_columns))
+}
+
+// Arguments can be unnamed, like `integer` or `double precision`.
+fn parse_function_argument(
+    dialect: &dyn Dialect,
+    argument_ddl: &str,
+) -> Result<Column, error::CodegenError> {
+    parse_column_definition(
+        dialect,
+        &format!("{} {argument_ddl}", dialect.quote_identifier("_")),
+    )
+    .map(|mut column| {
+        column.name = String::new();
+        column
+    })
+    .or_else(|_| parse_column_definition(dialect, argument_ddl))
+}
+
+fn parse_column_names(
+    cursor: &mut Cursor,
+    tokens: &[Token],
+) -> Result<Vec<String>, error::CodegenError> {
+    let column_tokens = cursor
+        .parse_parenthesized()
+        .ok_or_else(|| invalid_statement(tokens))?;
+    lexer::split_by_commas(&column_tokens)
+        .into_iter()
+        .map(|(start, end)| {
+            Cursor::new(&column_tokens[start..end])
+                .parse_identifier()
+                .ok_or_else(|| invalid_statement(tokens))
+        })
+        .collect()
+}
+
+fn invalid_statement(tokens: &[Token]) -> error::CodegenError {
+    error::CodegenError::SchemaError(format!(
+        "Invalid statement: {}",
+        lexer::to_sql(tokens).trim()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::PostgresDialect;
+
+    fn parse(ddl: &str) -> Option<SchemaChange> {
+        SchemaChange::from_tokens(&PostgresDialect::new(), &lexer::tokenize(ddl).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn skip_pg_dump_settings() {
+        assert!(parse("\\restrict key\nSET statement_timeout = 0").is_none());
+        assert!(parse("SELECT pg_catalog.set_config('search_path', '', false)").is_none());
+        assert!(parse("CREATE SEQUENCE public.users_id_seq AS integer START WITH 1").is_none());
+        assert!(parse("COMMENT ON EXTENSION plpgsql IS 'PL/pgSQL'").is_none());
+    }
+
+    #[test]
+    fn parse_pg_dump_constraints() {
+        let schema_change =
+            parse("ALTER TABLE ONLY public.users ADD CONSTRAINT users_pkey PRIMARY KEY (id)");
+        match schema_change {
+            Some(SchemaChange::AlterTable {
+                table_name,
+                actions,
+                ..
+            }) => {
+                assert_eq!(table_name, "users");
+                assert!(matches!(
+                    &actions[..],
+                    [AlterTableAction::AddPrimaryKey { column_names }] if column_names == &["id"]
+                ));
+            }
+            _ => panic!("Expected the ALTER TABLE statement"),
+        }
+    }
+
+    #[test]
+    fn fold_partitions() {
+        match parse("CREATE TABLE events_2024 PARTITION OF events FOR VALUES IN (2024)") {
+            Some(SchemaChange::AlterTable {
+                table_name,
+                actions,
+                ..
+            }) => {
+                assert_eq!(table_name, "events");
+                assert!(matches!(
+                    &actions[..],
+                    [AlterTableAction::AttachPartition { partition_name }] if partition_name == "events_2024"
+                ));
+            }
+            _ => panic!("Expected the partition to be attached"),
+        }
+        match parse("ALTER TABLE ONLY events ATTACH PARTITION public.events_2023 DEFAULT") {
+            Some(SchemaChange::AlterTable { actions, .. }) => assert!(matches!(
+                &actions[..],
+                [AlterTableAction::AttachPartition { partition_name }] if partition_name == "events_2023"
+            )),
+            _ => panic!("Expected the ALTER TABLE statement"),
+        }
+    }
+
+    #[test]
+    fn keep_default_value_whitespace() {
+        match parse("ALTER TABLE ONLY public.users ALTER COLUMN id SET DEFAULT nextval('public.users_id_seq'::regclass)") {
+            Some(SchemaChange::AlterTable { actions, .. }) => assert!(matches!(
+                &actions[..],
+                [AlterTableAction::SetDefault { default_value, .. }] if default_value == "nextval('public.users_id_seq'::regclass)"
+            )),
+            _ => panic!("Expected the ALTER TABLE statement"),
+        }
+        match parse(
+            "ALTER TABLE users ADD COLUMN created_at timestamp with time zone DEFAULT now()",
+        ) {
+            Some(SchemaChange::AlterTable { actions, .. }) => match &actions[..] {
+                [AlterTableAction::AddColumn { column, .. }] => {
+                    assert_eq!(column.default_value.as_deref(), Some("now()"))
+                }
+                _ => panic!("Expected the column to be added"),
+            },
+            _ => panic!("Expected the ALTER TABLE statement"),
+        }
+    }
+
+    #[test]
+    fn skip_unsupported_alter_table_actions() {
+        match parse("ALTER TABLE public.users OWNER TO postgres") {
+            Some(SchemaChange::AlterTable { actions, .. }) => assert!(actions.is_empty()),
+            _ => panic!("Expected the ALTER TABLE statement"),
+        }
+    }
+
+    #[test]
+    fn parse_comments() {
+        assert!(matches!(
+            parse("COMMENT ON COLUMN public.users.name IS 'Full name'"),
+            Some(SchemaChange::CommentOnColumn { table_name, column_name, description })
+                if table_name == "users" && column_name == "name"
+                    && description.as_deref() == Some("Full name")
+        ));
+        assert!(matches!(
+            parse("COMMENT ON TABLE users IS NULL"),
+            Some(SchemaChange::CommentOnTable {
+                description: None,
+                ..
+            })
+        ));
+    }
+}
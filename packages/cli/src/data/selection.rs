@@ -7,6 +7,7 @@ pub struct Selection<'a> {
     pub table: &'a data::Table,
     pub column_name: String,
     pub column: &'a data::Column,
+    pub json_access: Option<data::JsonAccess>,
 }
 
 impl<'a> Selection<'a> {
@@ -23,6 +24,7 @@ impl<'a> Selection<'a> {
             table,
             column_name,
             column,
+            json_access: None,
         }
     }
 }
@@ -35,6 +37,7 @@ impl<'a> Clone for Selection<'a> {
             table: self.table,
             column_name: self.column_name.clone(),
             column: self.column,
+            json_access: self.json_access.clone(),
         }
     }
 }
@@ -43,7 +46,8 @@ impl<'a> Clone for Selection<'a> {
 mod tests {
     use crate::data;
     use sqlparser::ast::{
-        DataType, Ident, ObjectName, SelectItem, TableAlias, TableFactor, TableWithJoins,
+        DataType, Expr, Function, FunctionArg, Ident, ObjectName, SelectItem, TableAlias,
+        TableFactor, TableWithJoins, Value,
     };
 
     fn create_primary_id_column() -> data::Column {
@@ -190,4 +194,73 @@ mod tests {
             "public.alias.id,public.alias.name"
         );
     }
+
+    #[test]
+    fn project_functions() {
+        let mut public_database = create_public_database();
+        let users_table = create_users_table(None);
+        public_database.functions = vec![
+            data::Function::new(
+                "search_users".to_string(),
+                vec![data::Column::new(
+                    "query".to_string(),
+                    DataType::Text,
+                    false,
+                    false,
+                    false,
+                    None,
+                )],
+                users_table.columns,
+                true,
+            ),
+            data::Function::new(
+                "count_users".to_string(),
+                vec![],
+                vec![data::Column::new(
+                    "count_users".to_string(),
+                    DataType::BigInt(None),
+                    false,
+                    false,
+                    false,
+                    None,
+                )],
+                false,
+            ),
+        ];
+
+        let from_search_users = vec![TableWithJoins {
+            relation: TableFactor::Table {
+                name: ObjectName(vec![Ident::new("search_users")]),
+                alias: None,
+                args: vec![FunctionArg::Unnamed(Expr::Value(Value::Null))],
+                with_hints: vec![],
+            },
+            joins: vec![],
+        }];
+        let select_items = vec![
+            SelectItem::Wildcard,
+            SelectItem::ExprWithAlias {
+                expr: Expr::Function(Function {
+                    name: ObjectName(vec![Ident::new("count_users")]),
+                    args: vec![],
+                    over: None,
+                    distinct: false,
+                }),
+                alias: Ident::new("total"),
+            },
+        ];
+
+        let mut projection =
+            data::Projection::from_tables_with_joins(&public_database, &from_search_users).unwrap();
+        projection.filter_by_select_items(&select_items).unwrap();
+
+        assert_eq!(
+            projection_source_to_string(&projection),
+            "public.search_users.id,public.search_users.name,public.count_users.count_users"
+        );
+        assert_eq!(
+            projection_target_to_string(&projection),
+            "public.search_users.id,public.search_users.name,public.count_users.total"
+        );
+    }
 }
@@ -1,11 +1,15 @@
 use super::column::Column;
+use super::{AlterTableAction, TableDefinition};
 use crate::{error, utils};
-use sqlparser::ast::Statement;
+use sqlparser::ast::{Statement, TableConstraint};
 
 #[derive(Debug)]
 pub struct Table {
     pub name: String,
     pub columns: Vec<Column>,
+    pub description: Option<String>,
+    pub alias: Option<String>,
+    pub is_materialized_view: bool,
 }
 
 impl Clone for Table {
@@ -13,43 +17,209 @@ impl Clone for Table {
         Table {
             name: self.name.clone(),
             columns: self.columns.clone(),
+            description: self.description.clone(),
+            alias: self.alias.clone(),
+            is_materialized_view: self.is_materialized_view,
         }
     }
 }
 
 impl Table {
     pub fn new(name: String, columns: Vec<Column>) -> Table {
-        Table { name, columns }
+        Table {
+            name,
+            columns,
+            description: None,
+            alias: None,
+            is_materialized_view: false,
+        }
     }
 
-    pub fn from_statement(statement: &Statement) -> Result<Table, error::CodegenError> {
-        if let Statement::CreateTable { columns, name, .. } = statement {
+    pub fn from_table_definition(
+        table_definition: &TableDefinition,
+    ) -> Result<Table, error::CodegenError> {
+        let statement = &table_definition.statement;
+        if let Statement::CreateTable {
+            columns,
+            name,
+            constraints,
+            ..
+        } = statement
+        {
             let columns: Vec<Column> = columns
                 .iter()
-                .map(|column| Column::from_column_definition(column))
+                .map(|column| {
+                    Column::from_column_definition(
+                        column,
+                        &table_definition.get_column_attributes(&column.name.value),
+                    )
+                })
                 .collect();
-            return Ok(Table::new(utils::object_name_to_string(name), columns));
+            let mut table = Table::new(utils::object_name_to_string(name), columns);
+            table.alias = table_definition.table_alias.clone();
+            table.is_materialized_view = table_definition.is_materialized_view;
+            for constraint in constraints {
+                if let TableConstraint::Unique {
+                    columns,
+                    is_primary,
+                    ..
+                } = constraint
+                {
+                    let column_names = columns
+                        .iter()
+                        .map(|column| column.value.clone())
+                        .collect::<Vec<String>>();
+                    table.add_unique_constraint(&column_names, *is_primary)?;
+                }
+            }
+            return Ok(table);
         }
         Err(error::CodegenError::SchemaError(format!(
             "Expected a CREATE TABLE statement, got: {statement}",
         )))
     }
 
-    #[allow(dead_code)]
     pub fn has_column(&self, column_name: &str) -> bool {
         self.columns.iter().any(|column| column.name == column_name)
     }
 
-    #[allow(dead_code)]
     pub fn find_column(&self, column_name: &str) -> Option<&Column> {
         self.columns
             .iter()
             .find(|column| column.name == column_name)
     }
+
+    pub fn find_column_mut(
+        &mut self,
+        column_name: &str,
+    ) -> Result<&mut Column, error::CodegenError> {
+        let error = self.column_not_found(column_name);
+        self.columns
+            .iter_mut()
+            .find(|column| column.name == column_name)
+            .ok_or(error)
+    }
+
+    fn column_not_found(&self, column_name: &str) -> error::CodegenError {
+        error::CodegenError::SchemaError(format!(
+            "Column \"{}.{column_name}\" not found",
+            self.name
+        ))
+    }
+
+    // Only a single column constraint makes the column unique on its own.
+    fn add_unique_constraint(
+        &mut self,
+        column_names: &[String],
+        is_primary_key: bool,
+    ) -> Result<(), error::CodegenError> {
+        let is_unique = column_names.len() == 1;
+        for column_name in column_names {
+            let column = self.find_column_mut(column_name)?;
+            if is_primary_key {
+                column.is_primary_key = true;
+                column.is_not_null = true;
+            }
+            if is_unique {
+                column.is_unique = true;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply_alter_table_action(
+        &mut self,
+        action: AlterTableAction,
+    ) -> Result<(), error::CodegenError> {
+        match action {
+            AlterTableAction::AddColumn {
+                column,
+                if_not_exists,
+            } => {
+                if self.has_column(&column.name) {
+                    if if_not_exists {
+                        return Ok(());
+                    }
+                    return Err(error::CodegenError::SchemaError(format!(
+                        "Column \"{}.{}\" already exists",
+                        self.name, column.name
+                    )));
+                }
+                self.columns.push(column);
+            }
+            AlterTableAction::DropColumn {
+                column_name,
+                if_exists,
+            } => {
+                if !self.has_column(&column_name) && !if_exists {
+                    return Err(self.column_not_found(&column_name));
+                }
+                self.columns.retain(|column| column.name != column_name);
+            }
+            AlterTableAction::RenameColumn {
+                column_name,
+                new_column_name,
+            } => {
+                self.find_column_mut(&column_name)?.name = new_column_name;
+            }
+            AlterTableAction::RenameTable { new_table_name } => {
+                self.name = new_table_name;
+            }
+            AlterTableAction::AlterColumnType {
+                column_name,
+                column: typed_column,
+            } => {
+                let column = self.find_column_mut(&column_name)?;
+                column.sql_type = typed_column.sql_type;
+                column.array_dimensions = typed_column.array_dimensions;
+            }
+            AlterTableAction::SetNotNull { column_name } => {
+                self.find_column_mut(&column_name)?.is_not_null = true;
+            }
+            AlterTableAction::DropNotNull { column_name } => {
+                self.find_column_mut(&column_name)?.is_not_null = false;
+            }
+            AlterTableAction::SetDefault {
+                column_name,
+                default_value,
+            } => {
+                self.find_column_mut(&column_name)?.default_value = Some(default_value);
+            }
+            AlterTableAction::DropDefault { column_name } => {
+                self.find_column_mut(&column_name)?.default_value = None;
+            }
+            AlterTableAction::SetIdentity {
+                column_name,
+                identity,
+            } => {
+                let column = self.find_column_mut(&column_name)?;
+                column.identity = Some(identity);
+                column.is_not_null = true;
+            }
+            AlterTableAction::AddPrimaryKey { column_names } => {
+                self.add_unique_constraint(&column_names, true)?;
+            }
+            AlterTableAction::AddUnique { column_names } => {
+                self.add_unique_constraint(&column_names, false)?;
+            }
+            AlterTableAction::AddCheck {
+                column_name,
+                allowed_values,
+            } => {
+                self.find_column_mut(&column_name)?.allowed_values = Some(allowed_values);
+            }
+            // The database removes the partition table.
+            AlterTableAction::AttachPartition { .. } => {}
+        }
+        Ok(())
+    }
 }
 
 impl PartialEq for Table {
     fn eq(&self, other: &Table) -> bool {
-        self.name == other.name && self.columns == other.columns
+        self.name == other.name
+            && self.columns == other.columns
+            && self.description == other.description
+            && self.alias == other.alias
     }
 }
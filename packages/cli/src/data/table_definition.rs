@@ -0,0 +1,1016 @@
+use super::{ColumnAttributes, ColumnIdentity};
+use crate::dialects::Dialect;
+use crate::error;
+use crate::lexer::{self, Cursor, Token, TokenKind};
+use sqlparser::{ast::Statement, parser::Parser};
+use std::collections::HashMap;
+
+const TABLE_CONSTRAINT_KEYWORDS: [&str; 7] = [
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "CHECK",
+    "FOREIGN",
+    "EXCLUDE",
+    "LIKE",
+];
+
+const COLUMN_CONSTRAINT_KEYWORDS: [&str; 10] = [
+    "CONSTRAINT",
+    "NOT",
+    "NULL",
+    "UNIQUE",
+    "PRIMARY",
+    "CHECK",
+    "REFERENCES",
+    "GENERATED",
+    "COLLATE",
+    "DEFAULT",
+];
+
+// Types with the precision or the modifiers the SQL parser doesn't support.
+const PRECISION_TYPES: [&str; 12] = [
+    "TIMESTAMP",
+    "DATETIME",
+    "DATETIME2",
+    "DATETIMEOFFSET",
+    "NVARCHAR",
+    "NCHAR",
+    "TIMESTAMPTZ",
+    "TIME",
+    "TIMETZ",
+    "INTERVAL",
+    "BIT",
+    "VARBIT",
+];
+
+// Types the SQL parser only reads with the size, like `BLOB(1024)`.
+const SIZED_TYPES: [&str; 4] = ["BLOB", "CLOB", "BINARY", "VARBINARY"];
+
+const INDEX_OPTION_KEYWORDS: [&str; 4] = ["CLUSTERED", "NONCLUSTERED", "ASC", "DESC"];
+
+// MySQL indexes defined together with the columns, like `KEY idx (email)`.
+const INDEX_KEYWORDS: [&str; 4] = ["KEY", "INDEX", "FULLTEXT", "SPATIAL"];
+
+const TYPE_MODIFIER_KEYWORDS: [&str; 12] = [
+    "WITH", "WITHOUT", "TIME", "ZONE", "VARYING", "YEAR", "MONTH", "DAY", "HOUR", "MINUTE",
+    "SECOND", "TO",
+];
+
+// The CREATE TABLE statement together with the column attributes the SQL
+// parser doesn't support, like identity and generated columns.
+#[derive(Debug)]
+pub struct TableDefinition {
+    pub statement: Statement,
+    pub column_attributes: HashMap<String, ColumnAttributes>,
+    pub table_alias: Option<String>,
+    pub is_materialized_view: bool,
+    // Parents of the table, like `INHERITS (parent)`, their columns are not
+    // listed in the table.
+    pub inherited_table_names: Vec<String>,
+}
+
+impl TableDefinition {
+    pub fn is_create_table(tokens: &[Token]) -> bool {
+        lexer::starts_with_keywords(tokens, &["CREATE", "TABLE"])
+            || lexer::starts_with_keywords(tokens, &["CREATE", "FOREIGN", "TABLE"])
+            || lexer::starts_with_keywords(tokens, &["CREATE", "UNLOGGED", "TABLE"])
+    }
+
+    pub fn from_tokens(
+        dialect: &dyn Dialect,
+        tokens: &[Token],
+    ) -> Result<TableDefinition, error::CodegenError> {
+        let mut tokens = tokens.to_vec();
+        let mut column_attributes = HashMap::new();
+        let mut inherited_table_names = vec![];
+        // The comments before the statement, like `/* @alias User */`.
+        let table_alias = tokens
+            .iter()
+            .take_while(|token| token.is_trivia())
+            .find_map(|token| token.annotation("alias"));
+        // The materialized views dumped as the tables, like `-- @materialized view`.
+        let is_materialized_view = tokens
+            .iter()
+            .take_while(|token| token.is_trivia())
+            .any(|token| token.has_annotation("materialized"));
+        if let Some(opening_index) = tokens.iter().position(|token| token.is_punctuation("(")) {
+            let closing_index = lexer::find_closing_parenthesis(&tokens, opening_index)
+                .ok_or_else(|| {
+                    error::CodegenError::SchemaError(format!(
+                        "Missing closing parenthesis in: {}",
+                        lexer::to_sql(&tokens)
+                    ))
+                })?;
+            let mut cursor = Cursor::new(&tokens[closing_index + 1..]);
+            if cursor.parse_keyword("INHERITS") {
+                let table_name_tokens = cursor.parse_parenthesized().unwrap_or_default();
+                let mut cursor = Cursor::new(&table_name_tokens);
+                while let Some(table_name) = cursor.parse_object_name() {
+                    inherited_table_names.push(table_name);
+                    cursor.parse_punctuation(",");
+                }
+            }
+            // The SQL parser doesn't support the clauses after the columns,
+            // like `PARTITION BY`, `INHERITS` or `SERVER`. The inherited
+            // columns are added by the database.
+            tokens[closing_index + 1..]
+                .iter_mut()
+                .for_each(|token| token.clear());
+            if let Some(kind_token) = tokens[..opening_index]
+                .iter_mut()
+                .find(|token| token.is_keyword("FOREIGN") || token.is_keyword("UNLOGGED"))
+            {
+                kind_token.clear();
+            }
+            let ranges = lexer::split_by_commas(&tokens[opening_index + 1..closing_index]);
+            let mut allowed_values_by_column = vec![];
+            let mut has_kept_element = false;
+            for (start, end) in ranges {
+                let start = opening_index + 1 + start;
+                let end = opening_index + 1 + end;
+                let comments = TableDefinition::get_column_comments(
+                    &tokens,
+                    (start, end),
+                    (opening_index, closing_index),
+                );
+                let mut cursor = Cursor::new(&tokens[start..end]);
+                if cursor.parse_keyword("CONSTRAINT") {
+                    cursor.parse_identifier();
+                }
+                let is_index = dialect.supports_inline_indexes()
+                    && INDEX_KEYWORDS
+                        .iter()
+                        .any(|keyword| cursor.parse_keyword(keyword));
+                // The SQL parser doesn't support all the check expressions, so
+                // the check constraints are removed together with a comma, and
+                // so are the indexes, which don't change the columns.
+                let is_check = !is_index && cursor.parse_keyword("CHECK");
+                if is_check {
+                    if let Some(expression_tokens) = cursor.parse_parenthesized() {
+                        allowed_values_by_column
+                            .extend(TableDefinition::parse_allowed_values(&expression_tokens));
+                    }
+                }
+                if is_index || is_check {
+                    let comma_index = if has_kept_element { start - 1 } else { end };
+                    tokens[start..end]
+                        .iter_mut()
+                        .for_each(|token| token.clear());
+                    if comma_index < closing_index {
+                        tokens[comma_index].clear();
+                    }
+                    continue;
+                }
+                let is_key = cursor.parse_keywords(&["PRIMARY", "KEY"]);
+                let is_unique = !is_key && cursor.parse_keyword("UNIQUE");
+                let opening_index = (start..end).find(|index| tokens[*index].is_punctuation("("));
+                if let Some(opening_index) = opening_index.filter(|_| is_key || is_unique) {
+                    // The unique indexes, like `UNIQUE KEY idx (email)`, are kept
+                    // as the unique constraints without the name.
+                    if is_unique
+                        && dialect.supports_inline_indexes()
+                        && (cursor.parse_keyword("KEY") || cursor.parse_keyword("INDEX"))
+                    {
+                        let unique_index = (start..end)
+                            .find(|index| tokens[*index].is_keyword("UNIQUE"))
+                            .unwrap();
+                        tokens[unique_index + 1..opening_index]
+                            .iter_mut()
+                            .for_each(|token| token.clear());
+                    }
+                    // The index options don't change the columns, like
+                    // `PRIMARY KEY CLUSTERED ([id] ASC) WITH (PAD_INDEX = OFF)`.
+                    if let Some(closing_index) =
+                        lexer::find_closing_parenthesis(&tokens, opening_index)
+                    {
+                        tokens[start..closing_index]
+                            .iter_mut()
+                            .filter(|token| {
+                                INDEX_OPTION_KEYWORDS
+                                    .iter()
+                                    .any(|keyword| token.is_keyword(keyword))
+                            })
+                            .for_each(|token| token.clear());
+                        tokens[closing_index + 1..end]
+                            .iter_mut()
+                            .for_each(|token| token.clear());
+                    }
+                }
+                has_kept_element = true;
+                if let Some((column_name, attributes)) = TableDefinition::extract_column_attributes(
+                    dialect,
+                    &mut tokens[start..end],
+                    &comments,
+                ) {
+                    column_attributes.insert(column_name, attributes);
+                }
+            }
+            for (column_name, allowed_values) in allowed_values_by_column {
+                let attributes: &mut ColumnAttributes =
+                    column_attributes.entry(column_name).or_default();
+                attributes.allowed_values = Some(allowed_values);
+            }
+        }
+
+        let ddl = lexer::to_sql(&tokens);
+        let mut statements = Parser::parse_sql(dialect.get_parser_dialect(), &ddl)?;
+        match statements.pop() {
+            Some(statement @ Statement::CreateTable { .. }) if statements.is_empty() => {
+                Ok(TableDefinition {
+                    statement,
+                    column_attributes,
+                    table_alias,
+                    is_materialized_view,
+                    inherited_table_names,
+                })
+            }
+            _ => Err(error::CodegenError::SchemaError(format!(
+                "Expected a CREATE TABLE statement, got: {ddl}"
+            ))),
+        }
+    }
+
+    // Returns the column limited by the check expression to the literal set,
+    // like `status IN ('active', 'disabled')` or the pg_dump's
+    // `status = ANY (ARRAY['active'::text, 'disabled'::text])`.
+    pub fn parse_allowed_values(tokens: &[Token]) -> Option<(String, Vec<String>)> {
+        let significant_tokens = lexer::significant_tokens(tokens);
+        let mut simplified_tokens = vec![];
+        let mut index = 0;
+        while index < significant_tokens.len() {
+            let token = significant_tokens[index];
+            index += 1;
+            if token.is_punctuation("(") || token.is_punctuation(")") {
+                continue;
+            }
+            // Casts, like `::text` or `::character varying[]`, are skipped.
+            if token.is_punctuation("::") {
+                while let Some(token) = significant_tokens.get(index) {
+                    let is_type_name = (token.kind == TokenKind::Word
+                        && !["IN", "ANY", "AND", "OR"]
+                            .iter()
+                            .any(|keyword| token.is_keyword(keyword)))
+                        || token.kind == TokenKind::QuotedIdentifier;
+                    let is_array_suffix = token.is_punctuation("[")
+                        && matches!(significant_tokens.get(index + 1), Some(token) if token.is_punctuation("]"));
+                    if is_type_name {
+                        index += 1;
+                    } else if is_array_suffix {
+                        index += 2;
+                    } else {
+                        break;
+                    }
+                }
+                continue;
+            }
+            simplified_tokens.push(token);
+        }
+
+        let column_name = simplified_tokens.first()?.identifier()?;
+        let value_tokens = match &simplified_tokens[1..] {
+            [in_keyword, value_tokens @ ..] if in_keyword.is_keyword("IN") => value_tokens,
+            [equals, any, array, opening, value_tokens @ .., closing]
+                if equals.is_punctuation("=")
+                    && any.is_keyword("ANY")
+                    && array.is_keyword("ARRAY")
+                    && opening.is_punctuation("[")
+                    && closing.is_punctuation("]") =>
+            {
+                value_tokens
+            }
+            _ => return None,
+        };
+        let mut allowed_values = vec![];
+        for (index, token) in value_tokens.iter().enumerate() {
+            if index % 2 == 0 {
+                allowed_values.push(token.string_value()?);
+            } else if !token.is_punctuation(",") {
+                return None;
+            }
+        }
+        if allowed_values.is_empty() {
+            return None;
+        }
+        Some((column_name, allowed_values))
+    }
+
+    pub fn get_column_attributes(&self, column_name: &str) -> ColumnAttributes {
+        self.column_attributes
+            .get(column_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // The comments of the column are the ones around its definition, except
+    // the ones on the line of the preceding comma, which belong to the
+    // preceding column, like `first_name text, -- @alias firstName`.
+    fn get_column_comments(
+        tokens: &[Token],
+        (start, end): (usize, usize),
+        (opening_index, closing_index): (usize, usize),
+    ) -> Vec<Token> {
+        let count_line_trivia = |tokens: &[Token]| {
+            tokens
+                .iter()
+                .take_while(|token| token.is_trivia() && !token.text.contains('\n'))
+                .count()
+        };
+        let start = if start > opening_index + 1 {
+            start + count_line_trivia(&tokens[start..end])
+        } else {
+            start
+        };
+        let trailing_tokens = if end < closing_index {
+            let trailing_start = end + 1;
+            &tokens[trailing_start
+                ..trailing_start + count_line_trivia(&tokens[trailing_start..closing_index])]
+        } else {
+            &[]
+        };
+        tokens[start..end]
+            .iter()
+            .chain(trailing_tokens)
+            .filter(|token| token.kind == TokenKind::Comment)
+            .cloned()
+            .collect()
+    }
+
+    // Removes the attributes from the column definition tokens and returns
+    // them together with the column name, the annotations are read from the
+    // comments of the column.
+    fn extract_column_attributes(
+        dialect: &dyn Dialect,
+        tokens: &mut [Token],
+        comments: &[Token],
+    ) -> Option<(String, ColumnAttributes)> {
+        let indexes = tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| !token.is_trivia())
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        let name_token = &tokens[*indexes.first()?];
+        if TABLE_CONSTRAINT_KEYWORDS
+            .iter()
+            .any(|keyword| name_token.is_keyword(keyword))
+        {
+            return None;
+        }
+        let column_name = name_token.identifier()?;
+        let mut attributes = ColumnAttributes {
+            alias: comments.iter().find_map(|token| token.annotation("alias")),
+            type_annotation: comments
+                .iter()
+                .find_map(|token| token.annotation_text("type")),
+            ..ColumnAttributes::default()
+        };
+
+        // Serial types are integers with a sequence behind them.
+        if let Some(&type_index) = indexes.get(1) {
+            let integer_type = match tokens[type_index].text.to_uppercase().as_str() {
+                "SMALLSERIAL" | "SERIAL2" => Some("SMALLINT"),
+                "SERIAL" | "SERIAL4" => Some("INTEGER"),
+                "BIGSERIAL" | "SERIAL8" => Some("BIGINT"),
+                _ => None,
+            };
+            if let Some(integer_type) = integer_type {
+                tokens[type_index].text = integer_type.to_string();
+                attributes.identity = Some(ColumnIdentity::Serial);
+            }
+        }
+
+        // The SQL parser drops the time zone and doesn't support the precision,
+        // like `timestamp(3) with time zone`, so the types are rewritten to
+        // their short names, like `TIMESTAMPTZ`.
+        if let Some(&type_index) = indexes.get(1) {
+            let type_name = tokens[type_index].text.to_uppercase();
+            if tokens[type_index].kind == TokenKind::Word
+                && PRECISION_TYPES.contains(&type_name.as_str())
+            {
+                let mut is_with_time_zone = false;
+                let mut is_varying = false;
+                let mut position = 2;
+                while let Some(&index) = indexes.get(position) {
+                    if tokens[index].is_punctuation("(") {
+                        let closing_index = lexer::find_closing_parenthesis(tokens, index)?;
+                        tokens[index..=closing_index]
+                            .iter_mut()
+                            .for_each(|token| token.clear());
+                        position = indexes
+                            .iter()
+                            .position(|index| *index == closing_index)
+                            .unwrap()
+                            + 1;
+                        continue;
+                    }
+                    if !TYPE_MODIFIER_KEYWORDS
+                        .iter()
+                        .any(|keyword| tokens[index].is_keyword(keyword))
+                    {
+                        break;
+                    }
+                    is_with_time_zone |= tokens[index].is_keyword("WITH");
+                    is_varying |= tokens[index].is_keyword("VARYING");
+                    tokens[index].clear();
+                    position += 1;
+                }
+                tokens[type_index].text = match type_name.as_str() {
+                    "TIMESTAMP" if is_with_time_zone => "TIMESTAMPTZ".to_string(),
+                    "TIME" if is_with_time_zone => "TIMETZ".to_string(),
+                    "BIT" if is_varying => "VARBIT".to_string(),
+                    _ => type_name,
+                };
+            }
+        }
+
+        // MySQL enums, like `ENUM('active', 'disabled')`, are typed as the
+        // union of their values and sets, like `SET('a', 'b')`, as text.
+        if let Some(&type_index) = indexes.get(1) {
+            let is_enum = tokens[type_index].is_keyword("ENUM");
+            if is_enum || tokens[type_index].is_keyword("SET") {
+                if let Some(&opening_index) = indexes.get(2) {
+                    if tokens[opening_index].is_punctuation("(") {
+                        let closing_index = lexer::find_closing_parenthesis(tokens, opening_index)?;
+                        if is_enum {
+                            attributes.allowed_values = lexer::significant_tokens(
+                                &tokens[opening_index + 1..closing_index],
+                            )
+                            .iter()
+                            .filter(|token| !token.is_punctuation(","))
+                            .map(|token| token.string_value())
+                            .collect::<Option<Vec<String>>>();
+                        }
+                        tokens[opening_index..=closing_index]
+                            .iter_mut()
+                            .for_each(|token| token.clear());
+                    }
+                }
+            }
+        }
+
+        // The T-SQL maximum sizes, like `VARCHAR(MAX)`, are removed.
+        if let (Some(&opening_index), Some(&size_index), Some(&closing_index)) =
+            (indexes.get(2), indexes.get(3), indexes.get(4))
+        {
+            if tokens[opening_index].is_punctuation("(")
+                && tokens[size_index].is_keyword("MAX")
+                && tokens[closing_index].is_punctuation(")")
+            {
+                tokens[opening_index..=closing_index]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+            }
+        }
+
+        // The types without the size, like `BLOB`, are read as the custom
+        // types.
+        if let Some(&type_index) = indexes.get(1) {
+            let is_sized =
+                matches!(indexes.get(2), Some(&index) if tokens[index].is_punctuation("("));
+            if !is_sized
+                && SIZED_TYPES
+                    .iter()
+                    .any(|type_name| tokens[type_index].is_keyword(type_name))
+            {
+                tokens[type_index].text =
+                    dialect.quote_identifier(&tokens[type_index].text.to_uppercase());
+                tokens[type_index].kind = TokenKind::QuotedIdentifier;
+            }
+        }
+
+        // The SQL parser doesn't support array types, so the dimensions are
+        // removed from the type, like `TEXT[]` or `INTEGER ARRAY[4]`.
+        let mut depth = 0;
+        let mut follows_array_keyword = false;
+        let mut position = 1;
+        while position < indexes.len() {
+            let index = indexes[position];
+            let token = &tokens[index];
+            if depth == 0
+                && COLUMN_CONSTRAINT_KEYWORDS
+                    .iter()
+                    .any(|keyword| token.is_keyword(keyword))
+            {
+                break;
+            }
+            if token.is_punctuation("(") {
+                depth += 1;
+            } else if token.is_punctuation(")") {
+                depth -= 1;
+            } else if depth == 0 && token.is_keyword("ARRAY") {
+                tokens[index].clear();
+                attributes.array_dimensions += 1;
+                follows_array_keyword = true;
+                position += 1;
+                continue;
+            } else if depth == 0 && token.is_punctuation("[") {
+                while position < indexes.len() && !tokens[indexes[position]].is_punctuation("]") {
+                    tokens[indexes[position]].clear();
+                    position += 1;
+                }
+                if position < indexes.len() {
+                    tokens[indexes[position]].clear();
+                }
+                if !follows_array_keyword {
+                    attributes.array_dimensions += 1;
+                }
+            }
+            follows_array_keyword = false;
+            position += 1;
+        }
+
+        let mut position = 2;
+        while position < indexes.len() {
+            let index = indexes[position];
+            let is_option = is_column_option(dialect, tokens, &indexes, position);
+            if tokens[index].is_keyword("DEFAULT") && position + 1 < indexes.len() {
+                let mut end = position + 1;
+                let mut depth = 0;
+                while end < indexes.len() {
+                    let token = &tokens[indexes[end]];
+                    if depth == 0
+                        && end > position + 1
+                        && (COLUMN_CONSTRAINT_KEYWORDS
+                            .iter()
+                            .any(|keyword| token.is_keyword(keyword))
+                            || is_column_option(dialect, tokens, &indexes, end))
+                    {
+                        break;
+                    }
+                    if token.is_punctuation("(") || token.is_punctuation("[") {
+                        depth += 1;
+                    } else if token.is_punctuation(")") || token.is_punctuation("]") {
+                        depth -= 1;
+                    }
+                    end += 1;
+                }
+                let last_index = indexes[end - 1];
+                attributes.default_value =
+                    Some(lexer::to_sql(&tokens[indexes[position + 1]..=last_index]));
+                // The named defaults, like `CONSTRAINT [DF_users_active] DEFAULT ((1))`.
+                let first_index =
+                    if position >= 4 && tokens[indexes[position - 2]].is_keyword("CONSTRAINT") {
+                        indexes[position - 2]
+                    } else {
+                        index
+                    };
+                tokens[first_index..=last_index]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+                position = end;
+            } else if is_option && tokens[index].is_keyword("IDENTITY") {
+                // The T-SQL identity, like `IDENTITY(1, 1)`.
+                attributes.identity = Some(ColumnIdentity::Always);
+                let mut last_index = index;
+                position += 1;
+                if let Some(&opening_index) = indexes.get(position) {
+                    if tokens[opening_index].is_punctuation("(") {
+                        last_index = lexer::find_closing_parenthesis(tokens, opening_index)?;
+                        position = indexes
+                            .iter()
+                            .position(|index| *index == last_index)
+                            .unwrap()
+                            + 1;
+                    }
+                }
+                tokens[index..=last_index]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+            } else if is_option
+                && [
+                    "UNSIGNED",
+                    "SIGNED",
+                    "ZEROFILL",
+                    "CLUSTERED",
+                    "NONCLUSTERED",
+                ]
+                .iter()
+                .any(|keyword| tokens[index].is_keyword(keyword))
+            {
+                tokens[index].clear();
+                position += 1;
+            } else if is_option
+                && (tokens[index].is_keyword("AUTO_INCREMENT")
+                    || tokens[index].is_keyword("AUTOINCREMENT"))
+            {
+                // Auto incremented columns are integers with a sequence behind
+                // them, like the serial types.
+                attributes.identity = Some(ColumnIdentity::Serial);
+                tokens[index].clear();
+                position += 1;
+            } else if is_option && tokens[index].is_keyword("COMMENT") {
+                let description = indexes
+                    .get(position + 1)
+                    .and_then(|&index| tokens[index].string_value());
+                if let Some(description) = description {
+                    if attributes.type_annotation.is_none() {
+                        attributes.type_annotation =
+                            lexer::find_annotation_text(&description, "type");
+                    }
+                    attributes.description = Some(description);
+                    tokens[indexes[position + 1]].clear();
+                }
+                tokens[index].clear();
+                position += 2;
+            } else if is_option
+                && (tokens[index].is_keyword("CHARACTER") || tokens[index].is_keyword("CHARSET"))
+            {
+                // The character set, like `CHARACTER SET utf8mb4`.
+                let length = if tokens[index].is_keyword("CHARACTER") {
+                    3
+                } else {
+                    2
+                };
+                indexes[position..(position + length).min(indexes.len())]
+                    .iter()
+                    .for_each(|&index| tokens[index].clear());
+                position += length;
+            } else if is_option
+                && tokens[index].is_keyword("ON")
+                && !["CASCADE", "RESTRICT", "SET", "NO"]
+                    .iter()
+                    .any(|keyword| matches_keywords(tokens, &indexes, position + 2, &[keyword]))
+            {
+                // The MySQL update expression, like `ON UPDATE CURRENT_TIMESTAMP`,
+                // unlike the actions of the foreign keys.
+                let mut last_position = (position + 2).min(indexes.len() - 1);
+                if let Some(&opening_index) = indexes.get(position + 3) {
+                    if tokens[opening_index].is_punctuation("(") {
+                        let closing_index = lexer::find_closing_parenthesis(tokens, opening_index)?;
+                        last_position = indexes
+                            .iter()
+                            .position(|index| *index == closing_index)
+                            .unwrap();
+                    }
+                }
+                tokens[index..=indexes[last_position]]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+                position = last_position + 1;
+            } else if tokens[index].is_keyword("CHECK") {
+                let opening_index = indexes.get(position + 1).copied()?;
+                let closing_index = lexer::find_closing_parenthesis(tokens, opening_index)?;
+                if let Some((checked_column_name, allowed_values)) =
+                    TableDefinition::parse_allowed_values(&tokens[opening_index + 1..closing_index])
+                {
+                    if checked_column_name.eq_ignore_ascii_case(&column_name) {
+                        attributes.allowed_values = Some(allowed_values);
+                    }
+                }
+                // The SQL parser doesn't support all the check expressions.
+                let first_index =
+                    if position >= 4 && tokens[indexes[position - 2]].is_keyword("CONSTRAINT") {
+                        indexes[position - 2]
+                    } else {
+                        index
+                    };
+                tokens[first_index..=closing_index]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+                position = indexes
+                    .iter()
+                    .position(|index| *index == closing_index)
+                    .unwrap()
+                    + 1;
+            } else if tokens[index].is_keyword("GENERATED") {
+                let identity = if matches_keywords(
+                    tokens,
+                    &indexes,
+                    position + 1,
+                    &["ALWAYS", "AS", "IDENTITY"],
+                ) {
+                    Some((ColumnIdentity::Always, position + 4))
+                } else if matches_keywords(
+                    tokens,
+                    &indexes,
+                    position + 1,
+                    &["BY", "DEFAULT", "AS", "IDENTITY"],
+                ) {
+                    Some((ColumnIdentity::ByDefault, position + 5))
+                } else {
+                    None
+                };
+                let mut last_index;
+                if let Some((identity, next_position)) = identity {
+                    attributes.identity = Some(identity);
+                    last_index = indexes[next_position - 1];
+                    position = next_position;
+                    // Skip the sequence options.
+                    if let Some(&opening_index) = indexes.get(next_position) {
+                        if tokens[opening_index].is_punctuation("(") {
+                            if let Some(closing_index) =
+                                lexer::find_closing_parenthesis(tokens, opening_index)
+                            {
+                                last_index = closing_index;
+                                position = indexes
+                                    .iter()
+                                    .position(|index| *index == closing_index)
+                                    .unwrap()
+                                    + 1;
+                            }
+                        }
+                    }
+                } else if matches_keywords(tokens, &indexes, position + 1, &["ALWAYS", "AS"]) {
+                    let opening_index = indexes.get(position + 3).copied()?;
+                    let closing_index = lexer::find_closing_parenthesis(tokens, opening_index)?;
+                    attributes.generation_expression = Some(
+                        lexer::to_sql(&tokens[opening_index + 1..closing_index])
+                            .trim()
+                            .to_string(),
+                    );
+                    last_index = closing_index;
+                    position = indexes
+                        .iter()
+                        .position(|index| *index == closing_index)
+                        .unwrap()
+                        + 1;
+                    if matches_keywords(tokens, &indexes, position, &["STORED"]) {
+                        last_index = indexes[position];
+                        position += 1;
+                    }
+                } else {
+                    position += 1;
+                    continue;
+                }
+                tokens[index..=last_index]
+                    .iter_mut()
+                    .for_each(|token| token.clear());
+            } else {
+                position += 1;
+            }
+        }
+        Some((column_name, attributes))
+    }
+}
+
+fn matches_keywords(
+    tokens: &[Token],
+    indexes: &[usize],
+    position: usize,
+    keywords: &[&str],
+) -> bool {
+    keywords.iter().enumerate().all(|(offset, keyword)| {
+        indexes
+            .get(position + offset)
+            .filter(|index| tokens[**index].is_keyword(keyword))
+            .is_some()
+    })
+}
+
+// Whether the token at the position starts the column option of the dialect,
+// like `CHARACTER SET utf8mb4`, unlike the `character varying` type.
+fn is_column_option(
+    dialect: &dyn Dialect,
+    tokens: &[Token],
+    indexes: &[usize],
+    position: usize,
+) -> bool {
+    let token = match indexes.get(position) {
+        Some(&index) => &tokens[index],
+        None => return false,
+    };
+    if !dialect
+        .get_column_option_keywords()
+        .iter()
+        .any(|keyword| token.is_keyword(keyword))
+    {
+        return false;
+    }
+    if token.is_keyword("CHARACTER") {
+        matches_keywords(tokens, indexes, position + 1, &["SET"])
+    } else if token.is_keyword("ON") {
+        matches_keywords(tokens, indexes, position + 1, &["UPDATE"])
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Table;
+    use crate::dialects::{MssqlDialect, MysqlDialect, PostgresDialect};
+
+    fn extract(column_definition: &str) -> (String, ColumnAttributes, String) {
+        extract_with_dialect(&PostgresDialect::new(), column_definition)
+    }
+
+    fn extract_with_dialect(
+        dialect: &dyn Dialect,
+        column_definition: &str,
+    ) -> (String, ColumnAttributes, String) {
+        let mut tokens = dialect.tokenize(column_definition).unwrap();
+        let comments = tokens.clone();
+        let (name, attributes) =
+            TableDefinition::extract_column_attributes(dialect, &mut tokens, &comments).unwrap();
+        (name, attributes, lexer::to_sql(&tokens))
+    }
+
+    #[test]
+    fn extract_default_value() {
+        let (name, attributes, ddl) = extract("\"tags\" TEXT DEFAULT '{}'::text[] NOT NULL");
+        assert_eq!(name, "tags");
+        assert_eq!(attributes.default_value, Some("'{}'::text[]".to_string()));
+        assert_eq!(ddl, "\"tags\" TEXT  NOT NULL");
+    }
+
+    #[test]
+    fn keep_postgres_type_after_default_value() {
+        let column_definition =
+            "status character varying(20) DEFAULT 'active'::character varying NOT NULL";
+        let (_, attributes, ddl) = extract(column_definition);
+        assert_eq!(
+            attributes.default_value,
+            Some("'active'::character varying".to_string())
+        );
+        assert_eq!(ddl, "status character varying(20)  NOT NULL");
+
+        let dialect = PostgresDialect::new();
+        let tokens = dialect
+            .tokenize(&format!("CREATE TABLE users ({column_definition})"))
+            .unwrap();
+        let table_definition = TableDefinition::from_tokens(&dialect, &tokens).unwrap();
+        let table = Table::from_table_definition(&table_definition).unwrap();
+        let column = table.find_column("status").unwrap();
+        assert!(column.is_not_null);
+        assert_eq!(
+            column.default_value,
+            Some("'active'::character varying".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_array_dimensions() {
+        let (_, attributes, ddl) = extract("matrix INTEGER[][3] NOT NULL");
+        assert_eq!(attributes.array_dimensions, 2);
+        assert_eq!(ddl, "matrix INTEGER NOT NULL");
+
+        let (_, attributes, ddl) = extract("tags text ARRAY[4]");
+        assert_eq!(attributes.array_dimensions, 1);
+        assert_eq!(ddl, "tags text ");
+    }
+
+    #[test]
+    fn normalize_types() {
+        let (_, _, ddl) = extract("created_at timestamp(3) with time zone NOT NULL");
+        assert_eq!(ddl, "created_at TIMESTAMPTZ    NOT NULL");
+
+        let (_, attributes, ddl) = extract("starts_at time without time zone[]");
+        assert_eq!(attributes.array_dimensions, 1);
+        assert_eq!(ddl, "starts_at TIME   ");
+
+        let (_, _, ddl) = extract("flags bit varying(8)");
+        assert_eq!(ddl, "flags VARBIT ");
+    }
+
+    #[test]
+    fn extract_alias() {
+        let (_, attributes, _) =
+            extract("\n  /* @alias firstName */\n  first_name character varying(50) NOT NULL");
+        assert_eq!(attributes.alias, Some("firstName".to_string()));
+
+        let (_, attributes, _) = extract(
+            "/* @type { theme: \"dark\" | \"light\" } @alias userSettings */ settings jsonb",
+        );
+        assert_eq!(
+            attributes.type_annotation,
+            Some("{ theme: \"dark\" | \"light\" }".to_string())
+        );
+        assert_eq!(attributes.alias, Some("userSettings".to_string()));
+    }
+
+    #[test]
+    fn attach_trailing_comments_to_preceding_column() {
+        let dialect = PostgresDialect::new();
+        let tokens = dialect
+            .tokenize(
+                "CREATE TABLE users (
+                  id integer, -- @alias userId
+                  first_name text, -- @alias firstName
+                  -- @alias lastName
+                  last_name text,
+                  /* @alias displayName */ display_name text,
+                  settings jsonb, /* @type { theme: string } */ -- @alias userSettings
+                  nickname text -- @alias nick
+                )",
+            )
+            .unwrap();
+        let table_definition = TableDefinition::from_tokens(&dialect, &tokens).unwrap();
+        let alias = |column_name: &str| table_definition.get_column_attributes(column_name).alias;
+        assert_eq!(alias("id"), Some("userId".to_string()));
+        assert_eq!(alias("first_name"), Some("firstName".to_string()));
+        assert_eq!(alias("last_name"), Some("lastName".to_string()));
+        assert_eq!(alias("display_name"), Some("displayName".to_string()));
+        assert_eq!(alias("settings"), Some("userSettings".to_string()));
+        assert_eq!(alias("nickname"), Some("nick".to_string()));
+        assert_eq!(
+            table_definition
+                .get_column_attributes("settings")
+                .type_annotation,
+            Some("{ theme: string }".to_string())
+        );
+        assert_eq!(
+            table_definition
+                .get_column_attributes("nickname")
+                .type_annotation,
+            None
+        );
+    }
+
+    #[test]
+    fn extract_allowed_values() {
+        let (_, attributes, ddl) = extract(
+            "status TEXT NOT NULL CONSTRAINT status_check CHECK (status IN ('active', 'disabled'))",
+        );
+        assert_eq!(
+            attributes.allowed_values,
+            Some(vec!["active".to_string(), "disabled".to_string()])
+        );
+        assert_eq!(ddl, "status TEXT NOT NULL ");
+
+        let tokens = lexer::tokenize(
+            "((status)::text = ANY ((ARRAY['active'::character varying, 'disabled'::character varying])::text[]))",
+        )
+        .unwrap();
+        assert_eq!(
+            TableDefinition::parse_allowed_values(&tokens),
+            Some((
+                "status".to_string(),
+                vec!["active".to_string(), "disabled".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn extract_identity() {
+        let (_, attributes, ddl) =
+            extract("id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 10) PRIMARY KEY");
+        assert_eq!(attributes.identity, Some(ColumnIdentity::ByDefault));
+        assert_eq!(ddl, "id BIGINT  PRIMARY KEY");
+
+        let (_, attributes, _) = extract("id SERIAL");
+        assert_eq!(attributes.identity, Some(ColumnIdentity::Serial));
+    }
+
+    #[test]
+    fn extract_generation_expression() {
+        let (_, attributes, ddl) =
+            extract("full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED");
+        assert_eq!(
+            attributes.generation_expression,
+            Some("first_name || ' ' || last_name".to_string())
+        );
+        assert_eq!(ddl, "full_name TEXT ");
+    }
+
+    #[test]
+    fn extract_mysql_column_options() {
+        let dialect = MysqlDialect::new();
+        let (name, attributes, ddl) = extract_with_dialect(
+            &dialect,
+            "`status` enum('active','disabled') CHARACTER SET utf8mb4 NOT NULL DEFAULT 'active' COMMENT 'Account status'",
+        );
+        assert_eq!(name, "status");
+        assert_eq!(
+            attributes.allowed_values,
+            Some(vec!["active".to_string(), "disabled".to_string()])
+        );
+        assert_eq!(attributes.default_value, Some("'active'".to_string()));
+        assert_eq!(attributes.description, Some("Account status".to_string()));
+        assert_eq!(ddl, "`status` enum    NOT NULL   ");
+
+        let (_, attributes, ddl) = extract_with_dialect(
+            &dialect,
+            "id int unsigned NOT NULL AUTO_INCREMENT PRIMARY KEY",
+        );
+        assert_eq!(attributes.identity, Some(ColumnIdentity::Serial));
+        assert_eq!(ddl, "id int  NOT NULL  PRIMARY KEY");
+
+        let (_, _, ddl) = extract_with_dialect(
+            &dialect,
+            "updated_at datetime(3) DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)",
+        );
+        assert_eq!(ddl, "updated_at DATETIME  ");
+
+        let (_, _, ddl) = extract_with_dialect(&dialect, "avatar blob");
+        assert_eq!(ddl, "avatar `BLOB`");
+    }
+
+    #[test]
+    fn extract_tsql_column_options() {
+        let dialect = MssqlDialect::new();
+        let (name, attributes, ddl) =
+            extract_with_dialect(&dialect, "[id] INT IDENTITY(1,1) NOT NULL");
+        assert_eq!(name, "id");
+        assert_eq!(attributes.identity, Some(ColumnIdentity::Always));
+        assert_eq!(ddl, "[id] INT  NOT NULL");
+
+        let (_, attributes, ddl) = extract_with_dialect(
+            &dialect,
+            "[bio] NVARCHAR(MAX) CONSTRAINT [DF_users_bio] DEFAULT (N'') NOT NULL",
+        );
+        assert_eq!(attributes.default_value, Some("(N'')".to_string()));
+        assert_eq!(ddl, "[bio] NVARCHAR  NOT NULL");
+    }
+}
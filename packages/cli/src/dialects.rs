@@ -0,0 +1,80 @@
+mod connection;
+mod dialect;
+mod mssql_dialect;
+mod mysql_connection;
+mod mysql_dialect;
+mod postgres_connection;
+mod postgres_dialect;
+mod sqlite_connection;
+mod sqlite_dialect;
+
+pub use connection::Connection;
+pub use dialect::Dialect;
+pub use mssql_dialect::MssqlDialect;
+pub use mysql_connection::MysqlConnection;
+pub use mysql_dialect::MysqlDialect;
+pub use postgres_connection::PostgresConnection;
+pub use postgres_dialect::PostgresDialect;
+pub use sqlite_connection::SqliteConnection;
+pub use sqlite_dialect::SqliteDialect;
+
+use crate::error;
+
+pub fn get_all() -> Vec<Box<dyn Dialect>> {
+    vec![
+        Box::new(PostgresDialect::new()),
+        Box::new(SqliteDialect::new()),
+        Box::new(MysqlDialect::new()),
+        Box::new(MssqlDialect::new()),
+    ]
+}
+
+pub fn from_name(name: &str) -> Result<Box<dyn Dialect>, error::CodegenError> {
+    let dialects = get_all();
+    let dialect_names = dialects
+        .iter()
+        .map(|dialect| format!("\"{}\"", dialect.name()))
+        .collect::<Vec<String>>()
+        .join(", ");
+    dialects
+        .into_iter()
+        .find(|dialect| dialect.name() == name)
+        .ok_or_else(|| {
+            error::CodegenError::ConfigError(format!(
+                "Unknown dialect \"{name}\" in the \"dialect\" config property, expected one of: {dialect_names}"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_unknown_dialect_names() {
+        assert_eq!(from_name("sqlite").unwrap().name(), "sqlite");
+        let error = from_name("oracle").unwrap_err();
+        assert!(matches!(
+            error,
+            error::CodegenError::ConfigError(message)
+                if message == "Unknown dialect \"oracle\" in the \"dialect\" config property, expected one of: \"postgres\", \"sqlite\", \"mysql\", \"mssql\""
+        ));
+    }
+
+    #[test]
+    fn tell_placeholders_by_dialect() {
+        let placeholders = |dialect: &dyn Dialect| {
+            dialect
+                .tokenize("SELECT $1, ?, ?2, :name, @p1")
+                .unwrap()
+                .iter()
+                .filter(|token| dialect.is_placeholder(token))
+                .map(|token| token.text.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(placeholders(&PostgresDialect::new()), ["$1"]);
+        assert_eq!(placeholders(&SqliteDialect::new()), ["?", "?2", ":name"]);
+        assert_eq!(placeholders(&MysqlDialect::new()), ["?"]);
+        assert_eq!(placeholders(&MssqlDialect::new()), ["@p1"]);
+    }
+}
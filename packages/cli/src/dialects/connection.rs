@@ -0,0 +1,19 @@
+use crate::error;
+
+// Database connection used by the migrations, the parameters and the query
+// results are text.
+pub trait Connection {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), error::CodegenError>;
+
+    fn execute(&mut self, sql: &str, params: &[&str]) -> Result<(), error::CodegenError>;
+
+    fn query(&mut self, sql: &str) -> Result<Vec<Vec<Option<String>>>, error::CodegenError>;
+
+    fn begin_transaction(&mut self) -> Result<(), error::CodegenError>;
+
+    fn commit_transaction(&mut self) -> Result<(), error::CodegenError>;
+
+    fn rollback_transaction(&mut self) -> Result<(), error::CodegenError>;
+
+    fn close(self: Box<Self>) -> Result<(), error::CodegenError>;
+}
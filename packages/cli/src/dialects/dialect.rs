@@ -0,0 +1,80 @@
+use super::Connection;
+use crate::lexer::{self, Token};
+use crate::{config, error, utils};
+use sqlparser::ast::DataType;
+use sqlparser::parser::ParserError;
+use std::fmt::Debug;
+
+pub trait Dialect: Debug {
+    // Name of the dialect in the config, like `postgres`.
+    fn name(&self) -> &'static str;
+
+    fn get_parser_dialect(&self) -> &dyn sqlparser::dialect::Dialect;
+
+    fn tokenize(&self, sql: &str) -> Result<Vec<Token>, ParserError> {
+        lexer::tokenize(sql)
+    }
+
+    fn is_placeholder(&self, token: &Token) -> bool;
+
+    // Returns the placeholder of the query parameter, the index starts at 1.
+    fn get_placeholder(&self, index: usize) -> String;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        utils::quote_identifier(identifier)
+    }
+
+    // Whether the indexes can be defined together with the columns, like
+    // `KEY idx_email (email)`.
+    fn supports_inline_indexes(&self) -> bool {
+        false
+    }
+
+    // Whether `migrate diff` can write the migrations, the statements of the
+    // schema differences are written in the Postgres syntax.
+    fn supports_migration_diff(&self) -> bool {
+        false
+    }
+
+    // Whether the unquoted identifiers are folded to lower case, so `Users`
+    // and `users` name the same table.
+    fn folds_identifiers_to_lower_case(&self) -> bool {
+        false
+    }
+
+    // Whether the `database` connection property is the path of the database
+    // file, which is relative to the config file.
+    fn has_database_file(&self) -> bool {
+        false
+    }
+
+    // The column options of the dialect the SQL parser doesn't support, like
+    // `AUTO_INCREMENT`, which are removed from the column definitions.
+    fn get_column_option_keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Returns the name of the SQL type without the parameters, like `NUMERIC`
+    // for `NUMERIC(10, 2)`.
+    fn get_sql_type_name(&self, sql_type: &DataType) -> String;
+
+    fn get_scalar_name(&self, sql_type_name: &str) -> Option<&'static str>;
+
+    fn get_scalar_names(&self) -> Vec<&'static str>;
+
+    fn connect(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<Box<dyn Connection>, error::CodegenError>;
+
+    fn get_migration_history_table_ddl(&self, table_name: &str) -> String;
+
+    // Returns a row only if the table exists in the current schema.
+    fn get_table_exists_sql(&self, table_name: &str) -> String;
+
+    // Introspects the database and returns its schema as DDL.
+    fn get_schema_ddl(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<String, error::CodegenError>;
+}
@@ -0,0 +1,186 @@
+use super::{Connection, Dialect};
+use crate::lexer::{self, Token, TokenKind};
+use crate::{config, error, utils};
+use sqlparser::ast::DataType;
+use sqlparser::dialect::MsSqlDialect;
+use sqlparser::parser::ParserError;
+
+// Scalar name and the built-in SQL types mapped to the scalar.
+const SCALAR_TYPES: [(&str, &[&str]); 17] = [
+    ("Boolean", &["BIT"]),
+    ("TinyInt", &["TINYINT"]),
+    ("SmallInt", &["SMALLINT"]),
+    ("Int", &["INT", "INTEGER"]),
+    ("BigInt", &["BIGINT"]),
+    ("Real", &["REAL"]),
+    ("Double", &["FLOAT"]),
+    ("Number", &["DECIMAL", "NUMERIC", "MONEY", "SMALLMONEY"]),
+    ("Char", &["CHAR", "VARCHAR", "NCHAR", "NVARCHAR"]),
+    ("Text", &["TEXT", "NTEXT", "XML", "SYSNAME"]),
+    ("Uuid", &["UNIQUEIDENTIFIER"]),
+    // `TIMESTAMP` is the row version in T-SQL.
+    (
+        "Blob",
+        &["BINARY", "VARBINARY", "IMAGE", "ROWVERSION", "TIMESTAMP"],
+    ),
+    ("Date", &["DATE"]),
+    ("Time", &["TIME"]),
+    ("DateTime", &["DATETIME", "DATETIME2", "SMALLDATETIME"]),
+    ("DateTimeOffset", &["DATETIMEOFFSET"]),
+    ("Geometry", &["GEOMETRY", "GEOGRAPHY"]),
+];
+
+#[derive(Debug)]
+pub struct MssqlDialect {
+    parser_dialect: MsSqlDialect,
+}
+
+impl MssqlDialect {
+    pub fn new() -> MssqlDialect {
+        MssqlDialect {
+            parser_dialect: MsSqlDialect {},
+        }
+    }
+
+    fn get_unsupported_connection_error() -> error::CodegenError {
+        error::CodegenError::ConfigError(
+            "The mssql dialect doesn't connect to the database, use the schema file or the migrations instead"
+                .to_string(),
+        )
+    }
+}
+
+impl Dialect for MssqlDialect {
+    fn name(&self) -> &'static str {
+        "mssql"
+    }
+
+    fn get_parser_dialect(&self) -> &dyn sqlparser::dialect::Dialect {
+        &self.parser_dialect
+    }
+
+    // The `GO` lines separate the batches of the scripts, like the semicolons.
+    fn tokenize(&self, sql: &str) -> Result<Vec<Token>, ParserError> {
+        let mut tokens = lexer::tokenize_bracket_identifiers(sql)?;
+        let is_line_break = |token: Option<&Token>| match token {
+            Some(token) => token.kind == TokenKind::Whitespace && token.text.contains('\n'),
+            None => true,
+        };
+        for index in 0..tokens.len() {
+            if tokens[index].is_keyword("GO")
+                && (index == 0 || is_line_break(tokens.get(index - 1)))
+                && is_line_break(tokens.get(index + 1))
+            {
+                tokens[index] = Token::new(TokenKind::Punctuation, ";".to_string());
+            }
+        }
+        Ok(tokens)
+    }
+
+    fn is_placeholder(&self, token: &Token) -> bool {
+        token.kind == TokenKind::Placeholder && token.text.starts_with('@')
+    }
+
+    fn get_placeholder(&self, index: usize) -> String {
+        format!("@p{index}")
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("[{}]", identifier.replace(']', "]]"))
+    }
+
+    fn get_column_option_keywords(&self) -> &'static [&'static str] {
+        &["IDENTITY", "CLUSTERED", "NONCLUSTERED"]
+    }
+
+    fn get_sql_type_name(&self, sql_type: &DataType) -> String {
+        let sql_type_name = match sql_type {
+            DataType::Char(_) => "CHAR",
+            DataType::Varchar(_) => "VARCHAR",
+            DataType::Uuid => "UUID",
+            DataType::Clob(_) => "CLOB",
+            DataType::Binary(_) => "BINARY",
+            DataType::Varbinary(_) => "VARBINARY",
+            DataType::Blob(_) => "BLOB",
+            DataType::Decimal(_, _) => "DECIMAL",
+            DataType::Float(_) => "FLOAT",
+            DataType::TinyInt(_) => "TINYINT",
+            DataType::SmallInt(_) => "SMALLINT",
+            DataType::Int(_) => "INT",
+            DataType::BigInt(_) => "BIGINT",
+            DataType::Real => "REAL",
+            DataType::Double => "FLOAT",
+            DataType::Boolean => "BIT",
+            DataType::Date => "DATE",
+            DataType::Time => "TIME",
+            DataType::Timestamp => "TIMESTAMP",
+            DataType::Interval => "INTERVAL",
+            DataType::Regclass => "REGCLASS",
+            DataType::Text => "TEXT",
+            DataType::String => "STRING",
+            DataType::Bytea => "BYTEA",
+            DataType::Custom(object_name) => return utils::object_name_to_string(object_name),
+            DataType::Array(element_type) => return self.get_sql_type_name(element_type),
+        };
+        sql_type_name.to_string()
+    }
+
+    fn get_scalar_name(&self, sql_type_name: &str) -> Option<&'static str> {
+        let sql_type_name = sql_type_name.to_uppercase();
+        SCALAR_TYPES
+            .iter()
+            .find(|(_, sql_type_names)| sql_type_names.contains(&sql_type_name.as_str()))
+            .map(|(scalar_name, _)| *scalar_name)
+    }
+
+    fn get_scalar_names(&self) -> Vec<&'static str> {
+        SCALAR_TYPES
+            .iter()
+            .map(|(scalar_name, _)| *scalar_name)
+            .collect()
+    }
+
+    fn connect(
+        &self,
+        _connection_config: &config::ConnectionConfig,
+    ) -> Result<Box<dyn Connection>, error::CodegenError> {
+        Err(MssqlDialect::get_unsupported_connection_error())
+    }
+
+    fn get_migration_history_table_ddl(&self, table_name: &str) -> String {
+        format!(
+            "IF OBJECT_ID(N'{table_name}', N'U') IS NULL
+CREATE TABLE {table_name} (
+  name NVARCHAR(255) NOT NULL PRIMARY KEY,
+  checksum NVARCHAR(255) NOT NULL,
+  applied_at DATETIMEOFFSET NOT NULL DEFAULT SYSDATETIMEOFFSET()
+);"
+        )
+    }
+
+    fn get_table_exists_sql(&self, table_name: &str) -> String {
+        format!("SELECT name FROM sys.tables WHERE object_id = OBJECT_ID(N'{table_name}', N'U')")
+    }
+
+    fn get_schema_ddl(
+        &self,
+        _connection_config: &config::ConnectionConfig,
+    ) -> Result<String, error::CodegenError> {
+        Err(MssqlDialect::get_unsupported_connection_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_go_batch_separators() {
+        let tokens = MssqlDialect::new()
+            .tokenize("CREATE TABLE [users] ([id] INT)\nGO\nSELECT 1 AS go")
+            .unwrap();
+        let statements = lexer::split_statements(tokens);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0][4].identifier(), Some("users".to_string()));
+    }
+}
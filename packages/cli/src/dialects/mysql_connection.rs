@@ -0,0 +1,72 @@
+use super::Connection;
+use crate::error;
+use mysql::prelude::Queryable;
+use mysql::{Conn, Row, Value};
+
+pub struct MysqlConnection {
+    connection: Conn,
+}
+
+impl MysqlConnection {
+    pub fn new(connection: Conn) -> MysqlConnection {
+        MysqlConnection { connection }
+    }
+
+    fn value_to_string(value: Value) -> Option<String> {
+        match value {
+            Value::NULL => None,
+            Value::Bytes(value) => Some(String::from_utf8_lossy(&value).to_string()),
+            Value::Int(value) => Some(value.to_string()),
+            Value::UInt(value) => Some(value.to_string()),
+            Value::Float(value) => Some(value.to_string()),
+            Value::Double(value) => Some(value.to_string()),
+            value => Some(value.as_sql(true)),
+        }
+    }
+}
+
+impl Connection for MysqlConnection {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), error::CodegenError> {
+        Ok(self.connection.query_drop(sql)?)
+    }
+
+    fn execute(&mut self, sql: &str, params: &[&str]) -> Result<(), error::CodegenError> {
+        let params = params
+            .iter()
+            .map(|param| Value::from(*param))
+            .collect::<Vec<Value>>();
+        Ok(self.connection.exec_drop(sql, params)?)
+    }
+
+    // The text protocol returns every value as text.
+    fn query(&mut self, sql: &str) -> Result<Vec<Vec<Option<String>>>, error::CodegenError> {
+        Ok(self
+            .connection
+            .query::<Row, _>(sql)?
+            .into_iter()
+            .map(|row| {
+                row.unwrap()
+                    .into_iter()
+                    .map(MysqlConnection::value_to_string)
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("START TRANSACTION")
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("COMMIT")
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("ROLLBACK")
+    }
+
+    // The connection is closed when it's dropped.
+    fn close(self: Box<Self>) -> Result<(), error::CodegenError> {
+        Ok(())
+    }
+}
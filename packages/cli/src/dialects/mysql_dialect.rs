@@ -0,0 +1,516 @@
+use super::{Connection, Dialect, MysqlConnection};
+use crate::lexer::{Token, TokenKind};
+use crate::{config, error, utils};
+use sqlparser::ast::DataType;
+use sqlparser::dialect::MySqlDialect;
+use std::collections::HashSet;
+
+// Scalar name and the built-in SQL types mapped to the scalar.
+const SCALAR_TYPES: [(&str, &[&str]); 20] = [
+    ("Boolean", &["BOOLEAN", "BOOL"]),
+    ("TinyInt", &["TINYINT"]),
+    ("SmallInt", &["SMALLINT"]),
+    ("MediumInt", &["MEDIUMINT"]),
+    ("Int", &["INT", "INTEGER"]),
+    ("BigInt", &["BIGINT"]),
+    ("Real", &["FLOAT"]),
+    ("Double", &["DOUBLE", "DOUBLE PRECISION", "REAL"]),
+    ("Numeric", &["DECIMAL", "NUMERIC", "DEC", "FIXED"]),
+    ("Char", &["CHAR", "VARCHAR"]),
+    (
+        "Text",
+        &["TEXT", "TINYTEXT", "MEDIUMTEXT", "LONGTEXT", "ENUM", "SET"],
+    ),
+    (
+        "Blob",
+        &[
+            "BINARY",
+            "VARBINARY",
+            "BLOB",
+            "TINYBLOB",
+            "MEDIUMBLOB",
+            "LONGBLOB",
+            "BIT",
+        ],
+    ),
+    ("Date", &["DATE"]),
+    ("Time", &["TIME"]),
+    ("DateTime", &["DATETIME"]),
+    ("Timestamp", &["TIMESTAMP"]),
+    ("Year", &["YEAR"]),
+    ("Json", &["JSON"]),
+    ("Point", &["POINT"]),
+    (
+        "Geometry",
+        &[
+            "GEOMETRY",
+            "LINESTRING",
+            "POLYGON",
+            "MULTIPOINT",
+            "MULTILINESTRING",
+            "MULTIPOLYGON",
+            "GEOMETRYCOLLECTION",
+        ],
+    ),
+];
+
+#[derive(Debug)]
+pub struct MysqlDialect {
+    parser_dialect: MySqlDialect,
+}
+
+impl MysqlDialect {
+    pub fn new() -> MysqlDialect {
+        MysqlDialect {
+            parser_dialect: MySqlDialect {},
+        }
+    }
+
+    fn connect_client(
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<MysqlConnection, error::CodegenError> {
+        let host = connection_config.host.as_ref().ok_or_else(|| {
+            error::CodegenError::ConfigError(
+                "The \"connection.host\" config property is required by the mysql dialect"
+                    .to_string(),
+            )
+        })?;
+        // The client is built without the TLS support.
+        let has_postgres_options = connection_config.ssl_mode.is_some()
+            || connection_config.ssl_root_cert.is_some()
+            || connection_config.ssl_cert.is_some()
+            || connection_config.ssl_key.is_some()
+            || connection_config.socket_dir.is_some();
+        if has_postgres_options {
+            return Err(error::CodegenError::ConfigError(
+                "The TLS and socket connection config properties aren't supported by the mysql dialect"
+                    .to_string(),
+            ));
+        }
+        let options = mysql::OptsBuilder::new()
+            .ip_or_hostname(Some(host))
+            .tcp_port(connection_config.port.unwrap_or(3306))
+            .user(connection_config.user.as_ref())
+            .pass(connection_config.password.as_ref())
+            .db_name(Some(&connection_config.database));
+        Ok(MysqlConnection::new(mysql::Conn::new(options)?))
+    }
+
+    // MySQL returns the string defaults without the quotes and MariaDB with
+    // them, like `'active'`.
+    fn get_default_value_ddl(default_value: &str, extra: &str) -> Option<String> {
+        if default_value == "NULL" {
+            None
+        } else if extra.contains("DEFAULT_GENERATED")
+            || default_value.starts_with('\'')
+            || default_value.parse::<f64>().is_ok()
+            || default_value.to_uppercase().starts_with("CURRENT_")
+        {
+            Some(default_value.to_string())
+        } else {
+            Some(utils::quote_string(default_value))
+        }
+    }
+
+    fn get_column_ddl(
+        &self,
+        row: &[Option<String>],
+        keys: &HashSet<(String, String, String)>,
+    ) -> String {
+        let value = |index: usize| row[index].as_deref().unwrap_or("");
+        let table_name = value(0);
+        let column_name = value(1);
+        let extra = value(5);
+        let mut column_options = vec![value(2).to_string()];
+        if !value(7).is_empty() {
+            column_options.push(format!(
+                "GENERATED ALWAYS AS ({}){}",
+                value(7),
+                if extra.contains("STORED") {
+                    " STORED"
+                } else {
+                    ""
+                }
+            ));
+        } else if let Some(default_value) = &row[4] {
+            column_options.extend(MysqlDialect::get_default_value_ddl(default_value, extra));
+        }
+        if value(3) == "NO" {
+            column_options.push("NOT NULL".to_string());
+        }
+        if extra.contains("auto_increment") {
+            column_options.push("AUTO_INCREMENT".to_string());
+        }
+        let has_key = |constraint_type: &str| {
+            keys.contains(&(
+                table_name.to_string(),
+                column_name.to_string(),
+                constraint_type.to_string(),
+            ))
+        };
+        if has_key("UNIQUE") {
+            column_options.push("UNIQUE".to_string());
+        }
+        if has_key("PRIMARY KEY") {
+            column_options.push("PRIMARY KEY".to_string());
+        }
+        if !value(6).is_empty() {
+            column_options.push(format!("COMMENT {}", utils::quote_string(value(6))));
+        }
+        format!(
+            "\t{} {}",
+            self.quote_identifier(column_name),
+            column_options.join(" ")
+        )
+    }
+}
+
+impl Dialect for MysqlDialect {
+    fn name(&self) -> &'static str {
+        "mysql"
+    }
+
+    fn get_parser_dialect(&self) -> &dyn sqlparser::dialect::Dialect {
+        &self.parser_dialect
+    }
+
+    fn is_placeholder(&self, token: &Token) -> bool {
+        token.kind == TokenKind::Placeholder && token.text == "?"
+    }
+
+    fn get_placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("`{}`", identifier.replace('`', "``"))
+    }
+
+    fn supports_inline_indexes(&self) -> bool {
+        true
+    }
+
+    fn get_column_option_keywords(&self) -> &'static [&'static str] {
+        &[
+            "UNSIGNED",
+            "SIGNED",
+            "ZEROFILL",
+            "AUTO_INCREMENT",
+            "COMMENT",
+            "CHARACTER",
+            "CHARSET",
+            "ON",
+        ]
+    }
+
+    fn get_sql_type_name(&self, sql_type: &DataType) -> String {
+        let sql_type_name = match sql_type {
+            // `TINYINT(1)` is the MySQL boolean.
+            DataType::TinyInt(Some(1)) => "BOOLEAN",
+            DataType::Char(_) => "CHAR",
+            DataType::Varchar(_) => "VARCHAR",
+            DataType::Uuid => "UUID",
+            DataType::Clob(_) => "CLOB",
+            DataType::Binary(_) => "BINARY",
+            DataType::Varbinary(_) => "VARBINARY",
+            DataType::Blob(_) => "BLOB",
+            DataType::Decimal(_, _) => "DECIMAL",
+            DataType::Float(_) => "FLOAT",
+            DataType::TinyInt(_) => "TINYINT",
+            DataType::SmallInt(_) => "SMALLINT",
+            DataType::Int(_) => "INT",
+            DataType::BigInt(_) => "BIGINT",
+            DataType::Real => "REAL",
+            DataType::Double => "DOUBLE",
+            DataType::Boolean => "BOOLEAN",
+            DataType::Date => "DATE",
+            DataType::Time => "TIME",
+            DataType::Timestamp => "TIMESTAMP",
+            DataType::Interval => "INTERVAL",
+            DataType::Regclass => "REGCLASS",
+            DataType::Text => "TEXT",
+            DataType::String => "STRING",
+            DataType::Bytea => "BYTEA",
+            DataType::Custom(object_name) => return utils::object_name_to_string(object_name),
+            DataType::Array(element_type) => return self.get_sql_type_name(element_type),
+        };
+        sql_type_name.to_string()
+    }
+
+    fn get_scalar_name(&self, sql_type_name: &str) -> Option<&'static str> {
+        let sql_type_name = sql_type_name.to_uppercase();
+        SCALAR_TYPES
+            .iter()
+            .find(|(_, sql_type_names)| sql_type_names.contains(&sql_type_name.as_str()))
+            .map(|(scalar_name, _)| *scalar_name)
+    }
+
+    fn get_scalar_names(&self) -> Vec<&'static str> {
+        SCALAR_TYPES
+            .iter()
+            .map(|(scalar_name, _)| *scalar_name)
+            .collect()
+    }
+
+    fn connect(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<Box<dyn Connection>, error::CodegenError> {
+        Ok(Box::new(MysqlDialect::connect_client(connection_config)?))
+    }
+
+    // The primary key can't be a TEXT column.
+    fn get_migration_history_table_ddl(&self, table_name: &str) -> String {
+        format!(
+            "CREATE TABLE IF NOT EXISTS {table_name} (
+  name VARCHAR(255) NOT NULL PRIMARY KEY,
+  checksum VARCHAR(255) NOT NULL,
+  applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);"
+        )
+    }
+
+    fn get_table_exists_sql(&self, table_name: &str) -> String {
+        format!(
+            "SELECT table_name FROM information_schema.tables
+WHERE table_schema = DATABASE() AND table_name = '{table_name}'"
+        )
+    }
+
+    fn get_schema_ddl(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<String, error::CodegenError> {
+        let mut connection = MysqlDialect::connect_client(connection_config)?;
+        let rows = connection.query(COLUMNS_QUERY)?;
+        let keys = connection
+            .query(KEYS_QUERY)?
+            .into_iter()
+            .map(|row| {
+                let value = |index: usize| row[index].clone().unwrap_or_default();
+                (value(0), value(1), value(2))
+            })
+            .collect::<HashSet<(String, String, String)>>();
+
+        let mut ddl = String::from("");
+        let mut prev_table_name: Option<&str> = None;
+        for row in &rows {
+            let table_name = row[0].as_deref().unwrap_or("");
+            if prev_table_name != Some(table_name) {
+                if prev_table_name.is_some() {
+                    ddl.push_str("\n);\n\n");
+                }
+                ddl.push_str(&format!(
+                    "CREATE TABLE {} (\n",
+                    self.quote_identifier(table_name)
+                ));
+            } else {
+                ddl.push_str(",\n");
+            }
+            ddl.push_str(&self.get_column_ddl(row, &keys));
+            prev_table_name = Some(table_name);
+        }
+        if prev_table_name.is_some() {
+            ddl.push_str("\n);\n");
+        }
+        Ok(ddl)
+    }
+}
+
+const COLUMNS_QUERY: &str = "SELECT
+  columns.TABLE_NAME AS table_name,
+  columns.COLUMN_NAME AS column_name,
+  columns.COLUMN_TYPE AS column_type,
+  columns.IS_NULLABLE AS is_nullable,
+  columns.COLUMN_DEFAULT AS column_default,
+  columns.EXTRA AS extra,
+  columns.COLUMN_COMMENT AS column_comment,
+  columns.GENERATION_EXPRESSION AS generation_expression
+FROM
+  information_schema.COLUMNS AS columns
+  JOIN information_schema.TABLES AS tables
+    ON tables.TABLE_SCHEMA = columns.TABLE_SCHEMA
+    AND tables.TABLE_NAME = columns.TABLE_NAME
+WHERE
+  columns.TABLE_SCHEMA = DATABASE()
+  AND tables.TABLE_TYPE = 'BASE TABLE'
+  AND columns.TABLE_NAME <> '_sql_codegen_migrations'
+ORDER BY
+  columns.TABLE_NAME ASC,
+  columns.ORDINAL_POSITION ASC;";
+
+// Only the unique constraints of a single column are a column option.
+const KEYS_QUERY: &str = "SELECT
+  key_column_usage.TABLE_NAME AS table_name,
+  key_column_usage.COLUMN_NAME AS column_name,
+  table_constraints.CONSTRAINT_TYPE AS constraint_type
+FROM
+  information_schema.KEY_COLUMN_USAGE AS key_column_usage
+  JOIN information_schema.TABLE_CONSTRAINTS AS table_constraints
+    ON table_constraints.CONSTRAINT_SCHEMA = key_column_usage.CONSTRAINT_SCHEMA
+    AND table_constraints.TABLE_NAME = key_column_usage.TABLE_NAME
+    AND table_constraints.CONSTRAINT_NAME = key_column_usage.CONSTRAINT_NAME
+WHERE
+  key_column_usage.TABLE_SCHEMA = DATABASE()
+  AND (
+    table_constraints.CONSTRAINT_TYPE = 'PRIMARY KEY'
+    OR (
+      table_constraints.CONSTRAINT_TYPE = 'UNIQUE'
+      AND NOT EXISTS (
+        SELECT 1
+        FROM information_schema.KEY_COLUMN_USAGE AS other_key_column_usage
+        WHERE
+          other_key_column_usage.CONSTRAINT_SCHEMA = key_column_usage.CONSTRAINT_SCHEMA
+          AND other_key_column_usage.TABLE_NAME = key_column_usage.TABLE_NAME
+          AND other_key_column_usage.CONSTRAINT_NAME = key_column_usage.CONSTRAINT_NAME
+          AND other_key_column_usage.COLUMN_NAME <> key_column_usage.COLUMN_NAME
+      )
+    )
+  );";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use sqlparser::ast::{Ident, ObjectName};
+
+    fn create_row(values: [Option<&str>; 8]) -> Vec<Option<String>> {
+        values
+            .iter()
+            .map(|value| value.map(|value| value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn get_sql_type_names() {
+        let dialect = MysqlDialect::new();
+        assert_eq!(
+            dialect.get_sql_type_name(&DataType::TinyInt(Some(1))),
+            "BOOLEAN"
+        );
+        assert_eq!(
+            dialect.get_sql_type_name(&DataType::TinyInt(Some(4))),
+            "TINYINT"
+        );
+        assert_eq!(
+            dialect.get_sql_type_name(&DataType::TinyInt(None)),
+            "TINYINT"
+        );
+        assert_eq!(
+            dialect.get_sql_type_name(&DataType::Custom(ObjectName(vec![Ident::new("MEDIUMINT")]))),
+            "MEDIUMINT"
+        );
+        assert_eq!(dialect.get_scalar_name("BOOLEAN"), Some("Boolean"));
+        assert_eq!(dialect.get_scalar_name("mediumint"), Some("MediumInt"));
+    }
+
+    #[test]
+    fn get_default_value_ddls() {
+        // MySQL returns the strings without the quotes.
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("active", ""),
+            Some("'active'".to_string())
+        );
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("it's", ""),
+            Some("'it''s'".to_string())
+        );
+        // MariaDB returns them quoted.
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("'active'", ""),
+            Some("'active'".to_string())
+        );
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("0", ""),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("CURRENT_TIMESTAMP", "DEFAULT_GENERATED"),
+            Some("CURRENT_TIMESTAMP".to_string())
+        );
+        assert_eq!(
+            MysqlDialect::get_default_value_ddl("uuid()", "DEFAULT_GENERATED"),
+            Some("uuid()".to_string())
+        );
+        assert_eq!(MysqlDialect::get_default_value_ddl("NULL", ""), None);
+    }
+
+    #[test]
+    fn get_column_ddls() {
+        let dialect = MysqlDialect::new();
+        let keys = HashSet::from([
+            (
+                "users".to_string(),
+                "id".to_string(),
+                "PRIMARY KEY".to_string(),
+            ),
+            (
+                "users".to_string(),
+                "email".to_string(),
+                "UNIQUE".to_string(),
+            ),
+        ]);
+        let id_ddl = dialect.get_column_ddl(
+            &create_row([
+                Some("users"),
+                Some("id"),
+                Some("int unsigned"),
+                Some("NO"),
+                None,
+                Some("auto_increment"),
+                Some(""),
+                Some(""),
+            ]),
+            &keys,
+        );
+        assert_eq!(
+            id_ddl,
+            "\t`id` int unsigned NOT NULL AUTO_INCREMENT PRIMARY KEY"
+        );
+        let email_ddl = dialect.get_column_ddl(
+            &create_row([
+                Some("users"),
+                Some("email"),
+                Some("varchar(255)"),
+                Some("NO"),
+                None,
+                Some(""),
+                Some("Login email"),
+                Some(""),
+            ]),
+            &keys,
+        );
+        assert_eq!(
+            email_ddl,
+            "\t`email` varchar(255) NOT NULL UNIQUE COMMENT 'Login email'"
+        );
+        // The keys of the other tables don't apply.
+        let author_email_ddl = dialect.get_column_ddl(
+            &create_row([
+                Some("posts"),
+                Some("email"),
+                Some("varchar(255)"),
+                Some("YES"),
+                Some("NULL"),
+                Some(""),
+                Some(""),
+                Some(""),
+            ]),
+            &keys,
+        );
+        assert_eq!(author_email_ddl, "\t`email` varchar(255)");
+
+        let database = data::Database::from_schema_ddl(
+            &dialect,
+            &format!("CREATE TABLE `users` (\n{id_ddl},\n{email_ddl}\n);"),
+        )
+        .unwrap();
+        let table = database.find_table("users").unwrap();
+        let id_column = table.find_column("id").unwrap();
+        assert!(id_column.is_primary_key);
+        assert_eq!(id_column.identity, Some(data::ColumnIdentity::Serial));
+        let email_column = table.find_column("email").unwrap();
+        assert!(email_column.is_unique && email_column.is_not_null);
+        assert_eq!(email_column.description, Some("Login email".to_string()));
+    }
+}
@@ -0,0 +1,62 @@
+use super::Connection;
+use crate::error;
+use postgres::types::ToSql;
+use postgres::{Client, SimpleQueryMessage};
+
+pub struct PostgresConnection {
+    client: Client,
+}
+
+impl PostgresConnection {
+    pub fn new(client: Client) -> PostgresConnection {
+        PostgresConnection { client }
+    }
+}
+
+impl Connection for PostgresConnection {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), error::CodegenError> {
+        Ok(self.client.batch_execute(sql)?)
+    }
+
+    fn execute(&mut self, sql: &str, params: &[&str]) -> Result<(), error::CodegenError> {
+        let params = params
+            .iter()
+            .map(|param| param as &(dyn ToSql + Sync))
+            .collect::<Vec<&(dyn ToSql + Sync)>>();
+        self.client.execute(sql, &params)?;
+        Ok(())
+    }
+
+    // The simple query protocol returns every value as text.
+    fn query(&mut self, sql: &str) -> Result<Vec<Vec<Option<String>>>, error::CodegenError> {
+        Ok(self
+            .client
+            .simple_query(sql)?
+            .iter()
+            .filter_map(|message| match message {
+                SimpleQueryMessage::Row(row) => Some(
+                    (0..row.len())
+                        .map(|index| row.get(index).map(|value| value.to_string()))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("BEGIN")
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("COMMIT")
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("ROLLBACK")
+    }
+
+    fn close(self: Box<Self>) -> Result<(), error::CodegenError> {
+        Ok(self.client.close()?)
+    }
+}
@@ -0,0 +1,68 @@
+use super::Connection;
+use crate::error;
+use rusqlite::types::ValueRef;
+
+pub struct SqliteConnection {
+    connection: rusqlite::Connection,
+}
+
+impl SqliteConnection {
+    pub fn new(connection: rusqlite::Connection) -> SqliteConnection {
+        SqliteConnection { connection }
+    }
+
+    fn value_to_string(value: ValueRef) -> Option<String> {
+        match value {
+            ValueRef::Null => None,
+            ValueRef::Integer(value) => Some(value.to_string()),
+            ValueRef::Real(value) => Some(value.to_string()),
+            ValueRef::Text(value) | ValueRef::Blob(value) => {
+                Some(String::from_utf8_lossy(value).to_string())
+            }
+        }
+    }
+}
+
+impl Connection for SqliteConnection {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), error::CodegenError> {
+        Ok(self.connection.execute_batch(sql)?)
+    }
+
+    fn execute(&mut self, sql: &str, params: &[&str]) -> Result<(), error::CodegenError> {
+        self.connection
+            .execute(sql, rusqlite::params_from_iter(params.iter()))?;
+        Ok(())
+    }
+
+    fn query(&mut self, sql: &str) -> Result<Vec<Vec<Option<String>>>, error::CodegenError> {
+        let mut statement = self.connection.prepare(sql)?;
+        let column_count = statement.column_count();
+        let mut rows = statement.query([])?;
+        let mut values = vec![];
+        while let Some(row) = rows.next()? {
+            values.push(
+                (0..column_count)
+                    .map(|index| Ok(SqliteConnection::value_to_string(row.get_ref(index)?)))
+                    .collect::<Result<Vec<Option<String>>, rusqlite::Error>>()?,
+            );
+        }
+        Ok(values)
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("BEGIN")
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("COMMIT")
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), error::CodegenError> {
+        self.batch_execute("ROLLBACK")
+    }
+
+    fn close(self: Box<Self>) -> Result<(), error::CodegenError> {
+        self.connection.close().map_err(|(_, error)| error)?;
+        Ok(())
+    }
+}
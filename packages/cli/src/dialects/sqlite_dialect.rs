@@ -0,0 +1,328 @@
+use super::{Connection, Dialect, SqliteConnection};
+use crate::lexer::{Token, TokenKind};
+use crate::{config, error, utils};
+use rusqlite::OpenFlags;
+use sqlparser::ast::DataType;
+use sqlparser::dialect::SQLiteDialect;
+
+// Scalar name of every type affinity, the declared types are mapped to the
+// affinities by the rules of SQLite.
+const AFFINITY_SCALARS: [(&str, &str); 5] = [
+    ("INTEGER", "Integer"),
+    ("TEXT", "Text"),
+    ("BLOB", "Blob"),
+    ("REAL", "Real"),
+    ("NUMERIC", "Number"),
+];
+
+#[derive(Debug)]
+pub struct SqliteDialect {
+    parser_dialect: SQLiteDialect,
+}
+
+struct SqliteColumn {
+    name: String,
+    declared_type: String,
+    is_not_null: bool,
+    default_value: Option<String>,
+    primary_key_index: i64,
+}
+
+impl SqliteDialect {
+    pub fn new() -> SqliteDialect {
+        SqliteDialect {
+            parser_dialect: SQLiteDialect {},
+        }
+    }
+
+    fn get_type_affinity(declared_type: &str) -> &'static str {
+        let declared_type = declared_type.to_uppercase();
+        if declared_type.contains("INT") {
+            "INTEGER"
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|name| declared_type.contains(name))
+        {
+            "TEXT"
+        } else if declared_type.contains("BLOB") || declared_type.trim().is_empty() {
+            "BLOB"
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|name| declared_type.contains(name))
+        {
+            "REAL"
+        } else {
+            "NUMERIC"
+        }
+    }
+
+    // The declared types can be any sequence of words, like `UNSIGNED BIG INT`,
+    // so the types the SQL parser doesn't read are quoted.
+    fn get_column_type_ddl(declared_type: &str) -> String {
+        let type_name = declared_type.split('(').next().unwrap_or("").trim();
+        if type_name.is_empty() {
+            "BLOB".to_string()
+        } else if type_name.contains(char::is_whitespace) {
+            utils::quote_identifier(type_name)
+        } else {
+            declared_type.to_string()
+        }
+    }
+
+    fn get_column_ddl(column: &SqliteColumn, is_unique: bool, is_rowid: bool) -> String {
+        let mut column_options = vec![SqliteDialect::get_column_type_ddl(&column.declared_type)];
+        if let Some(default_value) = &column.default_value {
+            column_options.push(format!("DEFAULT {default_value}"));
+        }
+        // The `INTEGER PRIMARY KEY` column is the alias of the row id, which is
+        // never null.
+        if column.is_not_null || is_rowid {
+            column_options.push("NOT NULL".to_string());
+        }
+        if is_unique {
+            column_options.push("UNIQUE".to_string());
+        }
+        if column.primary_key_index > 0 {
+            column_options.push("PRIMARY KEY".to_string());
+        }
+        format!(
+            "\t{} {}",
+            utils::quote_identifier(&column.name),
+            column_options.join(" ")
+        )
+    }
+
+    fn get_create_table_ddl(
+        connection: &rusqlite::Connection,
+        table_name: &str,
+    ) -> Result<String, error::CodegenError> {
+        let mut statement = connection.prepare(TABLE_INFO_QUERY)?;
+        let columns = statement
+            .query_map([table_name], |row| {
+                Ok(SqliteColumn {
+                    name: row.get("name")?,
+                    declared_type: row.get("type")?,
+                    is_not_null: row.get("notnull")?,
+                    default_value: row.get("dflt_value")?,
+                    primary_key_index: row.get("pk")?,
+                })
+            })?
+            .collect::<Result<Vec<SqliteColumn>, rusqlite::Error>>()?;
+        let mut statement = connection.prepare(UNIQUE_COLUMNS_QUERY)?;
+        let unique_column_names = statement
+            .query_map([table_name], |row| row.get("name"))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+
+        let primary_key_count = columns
+            .iter()
+            .filter(|column| column.primary_key_index > 0)
+            .count();
+        let columns_ddl = columns
+            .iter()
+            .map(|column| {
+                let is_unique = unique_column_names.contains(&column.name);
+                let is_rowid = primary_key_count == 1
+                    && column.primary_key_index > 0
+                    && column.declared_type.eq_ignore_ascii_case("INTEGER");
+                SqliteDialect::get_column_ddl(column, is_unique, is_rowid)
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+        Ok(format!(
+            "CREATE TABLE {} (\n{columns_ddl}\n);\n",
+            utils::quote_identifier(table_name)
+        ))
+    }
+}
+
+impl Dialect for SqliteDialect {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn get_parser_dialect(&self) -> &dyn sqlparser::dialect::Dialect {
+        &self.parser_dialect
+    }
+
+    fn is_placeholder(&self, token: &Token) -> bool {
+        token.kind == TokenKind::Placeholder
+            && (token.text.starts_with('?') || token.text.starts_with(':'))
+    }
+
+    fn get_placeholder(&self, index: usize) -> String {
+        format!("?{index}")
+    }
+
+    fn has_database_file(&self) -> bool {
+        true
+    }
+
+    fn get_column_option_keywords(&self) -> &'static [&'static str] {
+        &["AUTOINCREMENT"]
+    }
+
+    fn get_sql_type_name(&self, sql_type: &DataType) -> String {
+        match sql_type {
+            DataType::Custom(object_name) => utils::object_name_to_string(object_name),
+            // The parameters, like the length of `VARCHAR(255)`, don't change
+            // the affinity.
+            _ => sql_type
+                .to_string()
+                .split('(')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string(),
+        }
+    }
+
+    fn get_scalar_name(&self, sql_type_name: &str) -> Option<&'static str> {
+        let affinity = SqliteDialect::get_type_affinity(sql_type_name);
+        AFFINITY_SCALARS
+            .iter()
+            .find(|(affinity_name, _)| *affinity_name == affinity)
+            .map(|(_, scalar_name)| *scalar_name)
+    }
+
+    fn get_scalar_names(&self) -> Vec<&'static str> {
+        AFFINITY_SCALARS
+            .iter()
+            .map(|(_, scalar_name)| *scalar_name)
+            .collect()
+    }
+
+    fn connect(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<Box<dyn Connection>, error::CodegenError> {
+        let connection = rusqlite::Connection::open(&connection_config.database)?;
+        Ok(Box::new(SqliteConnection::new(connection)))
+    }
+
+    fn get_migration_history_table_ddl(&self, table_name: &str) -> String {
+        format!(
+            "CREATE TABLE IF NOT EXISTS {table_name} (
+  name TEXT NOT NULL PRIMARY KEY,
+  checksum TEXT NOT NULL,
+  applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);"
+        )
+    }
+
+    fn get_table_exists_sql(&self, table_name: &str) -> String {
+        format!("SELECT name FROM sqlite_master WHERE type = 'table' AND name = '{table_name}'")
+    }
+
+    fn get_schema_ddl(
+        &self,
+        connection_config: &config::ConnectionConfig,
+    ) -> Result<String, error::CodegenError> {
+        // The schema is only read, so a missing database file is an error
+        // instead of creating an empty one.
+        let connection = rusqlite::Connection::open_with_flags(
+            &connection_config.database,
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        let mut statement = connection.prepare(TABLES_QUERY)?;
+        let table_names = statement
+            .query_map([], |row| row.get("name"))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+        drop(statement);
+
+        let ddl = table_names
+            .iter()
+            .map(|table_name| SqliteDialect::get_create_table_ddl(&connection, table_name))
+            .collect::<Result<Vec<String>, error::CodegenError>>()?
+            .join("\n");
+        connection.close().map_err(|(_, error)| error)?;
+        Ok(ddl)
+    }
+}
+
+const TABLES_QUERY: &str = "SELECT
+  name
+FROM
+  sqlite_master
+WHERE
+  type = 'table'
+  AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'
+  AND name <> '_sql_codegen_migrations'
+ORDER BY
+  name ASC;";
+
+const TABLE_INFO_QUERY: &str = "SELECT
+  name,
+  type,
+  \"notnull\",
+  dflt_value,
+  pk
+FROM
+  pragma_table_info(?1)
+ORDER BY
+  cid ASC;";
+
+// Only the unique constraints of a single column are a column option.
+const UNIQUE_COLUMNS_QUERY: &str = "SELECT
+  index_info.name
+FROM
+  pragma_index_list(?1) AS index_list
+  JOIN pragma_index_info(index_list.name) AS index_info
+WHERE
+  index_list.\"unique\"
+  AND index_list.origin = 'u'
+GROUP BY
+  index_list.name
+HAVING
+  count(*) = 1;";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    #[test]
+    fn get_schema_ddl_from_database_file() {
+        let database_file_path = env::temp_dir().join("sql_codegen_sqlite_dialect_test.db");
+        fs::remove_file(&database_file_path).ok();
+        let database = database_file_path.to_str().unwrap().to_string();
+        rusqlite::Connection::open(&database)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE users (
+                  id INTEGER PRIMARY KEY,
+                  email VARCHAR(255) NOT NULL UNIQUE,
+                  score UNSIGNED BIG INT DEFAULT 0,
+                  avatar
+                );",
+            )
+            .unwrap();
+        let connection_config = config::ConnectionConfig {
+            database,
+            ..Default::default()
+        };
+        let ddl = SqliteDialect::new()
+            .get_schema_ddl(&connection_config)
+            .unwrap();
+        fs::remove_file(&database_file_path).ok();
+        assert_eq!(
+            ddl,
+            "CREATE TABLE \"users\" (
+\t\"id\" INTEGER NOT NULL PRIMARY KEY,
+\t\"email\" VARCHAR(255) NOT NULL UNIQUE,
+\t\"score\" \"UNSIGNED BIG INT\" DEFAULT 0,
+\t\"avatar\" BLOB
+);
+"
+        );
+    }
+
+    #[test]
+    fn map_type_affinity_to_scalars() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(dialect.get_scalar_name("UNSIGNED BIG INT"), Some("Integer"));
+        assert_eq!(dialect.get_scalar_name("NVARCHAR"), Some("Text"));
+        assert_eq!(dialect.get_scalar_name("DOUBLE"), Some("Real"));
+        assert_eq!(dialect.get_scalar_name(""), Some("Blob"));
+        assert_eq!(dialect.get_scalar_name("DATETIME"), Some("Number"));
+    }
+}
@@ -1,5 +1,9 @@
+use mysql;
+use native_tls;
 use postgres;
+use rusqlite;
 use sqlparser;
+use std::fmt;
 use std::io;
 
 #[derive(Debug)]
@@ -9,10 +13,30 @@ pub enum CodegenError {
     IoError(io::Error),
     ParserError(sqlparser::parser::ParserError),
     PostgresError(postgres::Error),
+    SqliteError(rusqlite::Error),
+    MysqlError(mysql::Error),
+    TlsError(native_tls::Error),
     SchemaError(String),
     QueryError(String),
 }
 
+impl fmt::Display for CodegenError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodegenError::CliError(message)
+            | CodegenError::ConfigError(message)
+            | CodegenError::SchemaError(message)
+            | CodegenError::QueryError(message) => write!(formatter, "{message}"),
+            CodegenError::IoError(error) => write!(formatter, "IO error: {error}"),
+            CodegenError::ParserError(error) => write!(formatter, "SQL parser error: {error}"),
+            CodegenError::PostgresError(error) => write!(formatter, "PostgreSQL error: {error}"),
+            CodegenError::SqliteError(error) => write!(formatter, "SQLite error: {error}"),
+            CodegenError::MysqlError(error) => write!(formatter, "MySQL error: {error}"),
+            CodegenError::TlsError(error) => write!(formatter, "TLS error: {error}"),
+        }
+    }
+}
+
 impl From<io::Error> for CodegenError {
     fn from(error: io::Error) -> CodegenError {
         CodegenError::IoError(error)
@@ -30,3 +54,21 @@ impl From<postgres::Error> for CodegenError {
         CodegenError::PostgresError(error)
     }
 }
+
+impl From<rusqlite::Error> for CodegenError {
+    fn from(error: rusqlite::Error) -> CodegenError {
+        CodegenError::SqliteError(error)
+    }
+}
+
+impl From<mysql::Error> for CodegenError {
+    fn from(error: mysql::Error) -> CodegenError {
+        CodegenError::MysqlError(error)
+    }
+}
+
+impl From<native_tls::Error> for CodegenError {
+    fn from(error: native_tls::Error) -> CodegenError {
+        CodegenError::TlsError(error)
+    }
+}
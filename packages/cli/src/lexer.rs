@@ -0,0 +1,606 @@
+use sqlparser::parser::ParserError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+    Word,
+    QuotedIdentifier,
+    String,
+    Number,
+    Placeholder,
+    Punctuation,
+    Whitespace,
+    Comment,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+impl Token {
+    pub fn new(kind: TokenKind, text: String) -> Token {
+        Token { kind, text }
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        self.kind == TokenKind::Whitespace || self.kind == TokenKind::Comment
+    }
+
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    pub fn is_punctuation(&self, punctuation: &str) -> bool {
+        self.kind == TokenKind::Punctuation && self.text == punctuation
+    }
+
+    // Removes the token from the SQL produced by `to_sql`.
+    pub fn clear(&mut self) {
+        self.kind = TokenKind::Whitespace;
+        self.text = String::new();
+    }
+
+    pub fn identifier(&self) -> Option<String> {
+        match self.kind {
+            TokenKind::Word => Some(self.text.clone()),
+            TokenKind::QuotedIdentifier => {
+                let quote = &self.text[self.text.len() - 1..];
+                let inner = &self.text[1..self.text.len() - 1];
+                Some(inner.replace(&quote.repeat(2), quote))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn string_value(&self) -> Option<String> {
+        if self.kind != TokenKind::String || !self.text.starts_with('\'') {
+            return None;
+        }
+        let inner = &self.text[1..self.text.len() - 1];
+        Some(inner.replace("''", "'"))
+    }
+
+    // Returns the value of the `@name value` annotation in the comment.
+    pub fn annotation(&self, name: &str) -> Option<String> {
+        if self.kind != TokenKind::Comment {
+            return None;
+        }
+        let text = self
+            .text
+            .trim_start_matches("--")
+            .trim_start_matches("/*")
+            .trim_end_matches("*/");
+        let mut words = text.split_whitespace();
+        words.find(|word| word.strip_prefix('@') == Some(name))?;
+        words.next().map(|word| word.to_string())
+    }
+
+    // Whether the comment contains the `@name` annotation, like `@materialized`.
+    pub fn has_annotation(&self, name: &str) -> bool {
+        if self.kind != TokenKind::Comment {
+            return false;
+        }
+        self.text
+            .trim_start_matches("--")
+            .trim_start_matches("/*")
+            .trim_end_matches("*/")
+            .split_whitespace()
+            .any(|word| word.strip_prefix('@') == Some(name))
+    }
+
+    // Returns the text of the `@name text` annotation in the comment, which
+    // can contain spaces, like `@type { theme: string }`.
+    pub fn annotation_text(&self, name: &str) -> Option<String> {
+        if self.kind != TokenKind::Comment {
+            return None;
+        }
+        let text = self
+            .text
+            .trim_start_matches("--")
+            .trim_start_matches("/*")
+            .trim_end_matches("*/");
+        find_annotation_text(text, name)
+    }
+}
+
+// The annotation text ends at the next annotation, like `@alias`.
+pub fn find_annotation_text(text: &str, name: &str) -> Option<String> {
+    let prefix = format!("@{name}");
+    let mut start = 0;
+    let text_start = loop {
+        let index = start + text[start..].find(&prefix)?;
+        let text_start = index + prefix.len();
+        let is_word_start = index == 0 || text[..index].ends_with(char::is_whitespace);
+        if is_word_start && text[text_start..].starts_with(char::is_whitespace) {
+            break text_start;
+        }
+        start = text_start;
+    };
+    let rest = &text[text_start..];
+    let text_end = rest
+        .match_indices(" @")
+        .map(|(index, _)| index)
+        .find(|index| rest[index + 2..].starts_with(char::is_alphabetic))
+        .unwrap_or(rest.len());
+    let annotation_text = rest[..text_end].trim();
+    if annotation_text.is_empty() {
+        None
+    } else {
+        Some(annotation_text.to_string())
+    }
+}
+
+// Tokenizes SQL while keeping the original text of every token, so the token
+// stream can be turned back into the exact same SQL.
+pub fn tokenize(sql: &str) -> Result<Vec<Token>, ParserError> {
+    tokenize_with_brackets(sql, false)
+}
+
+// The brackets quote the identifiers in T-SQL, like `[order]`, instead of
+// the array subscripts.
+pub fn tokenize_bracket_identifiers(sql: &str) -> Result<Vec<Token>, ParserError> {
+    tokenize_with_brackets(sql, true)
+}
+
+fn tokenize_with_brackets(sql: &str, bracket_identifiers: bool) -> Result<Vec<Token>, ParserError> {
+    let chars = sql.chars().collect::<Vec<char>>();
+    let mut tokens: Vec<Token> = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let start = index;
+        let char = chars[index];
+        let next_char = chars.get(index + 1).copied();
+        let kind = if char.is_whitespace() {
+            while index < chars.len() && chars[index].is_whitespace() {
+                index += 1;
+            }
+            TokenKind::Whitespace
+        } else if char == '\\' && (index == 0 || chars[index - 1] == '\n') {
+            // The psql meta-commands, like `\connect`, take the rest of the line.
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+            TokenKind::Comment
+        } else if char == '-' && next_char == Some('-') {
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+            TokenKind::Comment
+        } else if char == '/' && next_char == Some('*') {
+            let mut depth = 0;
+            loop {
+                if index >= chars.len() {
+                    return Err(ParserError::TokenizerError(
+                        "Unterminated block comment".to_string(),
+                    ));
+                }
+                if chars[index] == '/' && chars.get(index + 1) == Some(&'*') {
+                    depth += 1;
+                    index += 2;
+                } else if chars[index] == '*' && chars.get(index + 1) == Some(&'/') {
+                    depth -= 1;
+                    index += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    index += 1;
+                }
+            }
+            TokenKind::Comment
+        } else if char == '\'' {
+            index = end_of_quoted(&chars, index, '\'', false)?;
+            TokenKind::String
+        } else if (char == 'E' || char == 'e') && next_char == Some('\'') {
+            index = end_of_quoted(&chars, index + 1, '\'', true)?;
+            TokenKind::String
+        } else if char == '"' || char == '`' {
+            index = end_of_quoted(&chars, index, char, false)?;
+            TokenKind::QuotedIdentifier
+        } else if char == '[' && bracket_identifiers {
+            index = end_of_quoted(&chars, index, ']', false)?;
+            TokenKind::QuotedIdentifier
+        } else if char == '$' && matches!(next_char, Some(char) if char.is_ascii_digit()) {
+            index += 1;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            TokenKind::Placeholder
+        } else if char == '?' {
+            // The placeholders of the other dialects, like `?` or `?1`.
+            index += 1;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            TokenKind::Placeholder
+        } else if (char == ':' || char == '@')
+            && matches!(next_char, Some(char) if char.is_alphabetic() || char == '_')
+        {
+            // The named placeholders, like `:name` or `@name`.
+            index += 1;
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            TokenKind::Placeholder
+        } else if char == '$' {
+            index = end_of_dollar_quoted(&chars, index)?;
+            if index == start + 1 {
+                TokenKind::Punctuation
+            } else {
+                TokenKind::String
+            }
+        } else if char.is_ascii_digit() {
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            TokenKind::Number
+        } else if char.is_alphabetic() || char == '_' {
+            while index < chars.len()
+                && (chars[index].is_alphanumeric() || chars[index] == '_' || chars[index] == '$')
+            {
+                index += 1;
+            }
+            TokenKind::Word
+        } else {
+            index += punctuation_length(&chars[index..]);
+            TokenKind::Punctuation
+        };
+        tokens.push(Token::new(kind, chars[start..index].iter().collect()));
+    }
+    Ok(tokens)
+}
+
+fn end_of_quoted(
+    chars: &[char],
+    start: usize,
+    quote: char,
+    backslash_escapes: bool,
+) -> Result<usize, ParserError> {
+    let mut index = start + 1;
+    loop {
+        match chars.get(index) {
+            None => {
+                return Err(ParserError::TokenizerError(format!(
+                    "Unterminated {quote} quoted literal"
+                )));
+            }
+            Some('\\') if backslash_escapes => index += 2,
+            Some(char) if *char == quote => {
+                if chars.get(index + 1) == Some(&quote) {
+                    index += 2;
+                } else {
+                    return Ok(index + 1);
+                }
+            }
+            Some(_) => index += 1,
+        }
+    }
+}
+
+fn end_of_dollar_quoted(chars: &[char], start: usize) -> Result<usize, ParserError> {
+    let mut index = start + 1;
+    while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+        index += 1;
+    }
+    if chars.get(index) != Some(&'$') {
+        return Ok(start + 1);
+    }
+    let tag = &chars[start..index + 1];
+    index += 1;
+    while index + tag.len() <= chars.len() {
+        if &chars[index..index + tag.len()] == tag {
+            return Ok(index + tag.len());
+        }
+        index += 1;
+    }
+    Err(ParserError::TokenizerError(
+        "Unterminated dollar quoted string".to_string(),
+    ))
+}
+
+fn punctuation_length(chars: &[char]) -> usize {
+    let operators = [
+        "->>", "#>>", "::", "->", "#>", "<=", ">=", "<>", "!=", "||", "=>",
+    ];
+    for operator in operators {
+        let operator = operator.chars().collect::<Vec<char>>();
+        if chars.starts_with(&operator) {
+            return operator.len();
+        }
+    }
+    1
+}
+
+pub fn to_sql(tokens: &[Token]) -> String {
+    tokens.iter().map(|token| token.text.as_str()).collect()
+}
+
+// Splits tokens into statements on the top level semicolons. Statements made
+// of whitespace and comments only are dropped.
+pub fn split_statements(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut statements = vec![];
+    let mut statement = vec![];
+    for token in tokens {
+        if token.is_punctuation(";") {
+            statements.push(statement);
+            statement = vec![];
+        } else {
+            statement.push(token);
+        }
+    }
+    statements.push(statement);
+    statements
+        .into_iter()
+        .filter(|statement| statement.iter().any(|token| !token.is_trivia()))
+        .collect()
+}
+
+pub fn significant_tokens(tokens: &[Token]) -> Vec<&Token> {
+    tokens.iter().filter(|token| !token.is_trivia()).collect()
+}
+
+pub fn starts_with_keywords(tokens: &[Token], keywords: &[&str]) -> bool {
+    let tokens = significant_tokens(tokens);
+    tokens.len() >= keywords.len()
+        && keywords
+            .iter()
+            .zip(tokens.iter())
+            .all(|(keyword, token)| token.is_keyword(keyword))
+}
+
+// Returns the index of the parenthesis closing the one at the given index.
+pub fn find_closing_parenthesis(tokens: &[Token], opening_index: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(opening_index) {
+        if token.is_punctuation("(") {
+            depth += 1;
+        } else if token.is_punctuation(")") {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+// Splits the tokens into index ranges on the commas that are not nested in
+// parentheses or brackets.
+pub fn split_by_commas(tokens: &[Token]) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        if token.is_punctuation("(") || token.is_punctuation("[") {
+            depth += 1;
+        } else if token.is_punctuation(")") || token.is_punctuation("]") {
+            depth -= 1;
+        } else if token.is_punctuation(",") && depth == 0 {
+            ranges.push((start, index));
+            start = index + 1;
+        }
+    }
+    ranges.push((start, tokens.len()));
+    ranges
+}
+
+// Walks over the significant tokens of a statement. The whitespace and the
+// comments are kept, so the remaining tokens can be turned back into SQL.
+pub struct Cursor<'a> {
+    tokens: &'a [Token],
+    significant_indexes: Vec<usize>,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(tokens: &'a [Token]) -> Cursor<'a> {
+        Cursor {
+            tokens,
+            significant_indexes: (0..tokens.len())
+                .filter(|index| !tokens[*index].is_trivia())
+                .collect(),
+            position: 0,
+        }
+    }
+
+    fn get(&self, position: usize) -> Option<&'a Token> {
+        let tokens = self.tokens;
+        self.significant_indexes
+            .get(position)
+            .map(|index| &tokens[*index])
+    }
+
+    // Index of the current token in all the tokens.
+    fn get_index(&self) -> usize {
+        self.significant_indexes
+            .get(self.position)
+            .copied()
+            .unwrap_or(self.tokens.len())
+    }
+
+    pub fn peek(&self) -> Option<&'a Token> {
+        self.get(self.position)
+    }
+
+    pub fn advance(&mut self) -> Option<&'a Token> {
+        let token = self.peek();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    pub fn parse_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(token) if token.is_keyword(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Consumes all the keywords or none of them.
+    pub fn parse_keywords(&mut self, keywords: &[&str]) -> bool {
+        let matches = keywords.iter().enumerate().all(|(offset, keyword)| {
+            self.get(self.position + offset)
+                .filter(|token| token.is_keyword(keyword))
+                .is_some()
+        });
+        if matches {
+            self.position += keywords.len();
+        }
+        matches
+    }
+
+    pub fn parse_punctuation(&mut self, punctuation: &str) -> bool {
+        match self.peek() {
+            Some(token) if token.is_punctuation(punctuation) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn parse_identifier(&mut self) -> Option<String> {
+        let identifier = self.peek()?.identifier()?;
+        self.position += 1;
+        Some(identifier)
+    }
+
+    // Parses the possibly schema qualified name and returns its last part.
+    pub fn parse_object_name(&mut self) -> Option<String> {
+        self.parse_qualified_name()?.pop()
+    }
+
+    // Returns all the parts of the dotted name, like `schema.table.column`.
+    pub fn parse_qualified_name(&mut self) -> Option<Vec<String>> {
+        let mut names = vec![self.parse_identifier()?];
+        while self.parse_punctuation(".") {
+            names.push(self.parse_identifier()?);
+        }
+        Some(names)
+    }
+
+    // Returns the tokens enclosed in the parentheses starting at the current
+    // position.
+    pub fn parse_parenthesized(&mut self) -> Option<Vec<Token>> {
+        if !self.peek()?.is_punctuation("(") {
+            return None;
+        }
+        let opening_index = self.get_index();
+        let closing_index = find_closing_parenthesis(self.tokens, opening_index)?;
+        self.position = self
+            .significant_indexes
+            .iter()
+            .take_while(|index| **index <= closing_index)
+            .count();
+        Some(self.tokens[opening_index + 1..closing_index].to_vec())
+    }
+
+    pub fn rest(&self) -> Vec<Token> {
+        self.tokens[self.get_index()..].to_vec()
+    }
+
+    pub fn rest_to_sql(&self) -> String {
+        to_sql(&self.rest()).trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(sql: &str) -> Vec<TokenKind> {
+        tokenize(sql)
+            .unwrap()
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_keeps_original_text() {
+        let sql = "CREATE TABLE \"users\" (\n\t-- comment\n\tname text DEFAULT 'it''s' /* a */\n);";
+        assert_eq!(to_sql(&tokenize(sql).unwrap()), sql);
+    }
+
+    #[test]
+    fn tokenize_literals() {
+        assert_eq!(
+            kinds("SELECT $1, 'a', $$b;c$$, $tag$d$tag$, \"e\", 1.5"),
+            vec![
+                TokenKind::Word,
+                TokenKind::Placeholder,
+                TokenKind::Punctuation,
+                TokenKind::String,
+                TokenKind::Punctuation,
+                TokenKind::String,
+                TokenKind::Punctuation,
+                TokenKind::String,
+                TokenKind::Punctuation,
+                TokenKind::QuotedIdentifier,
+                TokenKind::Punctuation,
+                TokenKind::Number,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_placeholders() {
+        assert_eq!(
+            kinds("WHERE a = ? AND b = ?12 AND c = :name AND d::text = $1 AND e = @name"),
+            vec![
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Placeholder,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Placeholder,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Placeholder,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Placeholder,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Placeholder,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_bracket_identifiers_in_tsql() {
+        let tokens = tokenize_bracket_identifiers("SELECT [order]]s], tags[1]").unwrap();
+        assert_eq!(tokens[2].identifier(), Some("order]s".to_string()));
+        assert_eq!(
+            kinds("SELECT tags[1]"),
+            vec![
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Punctuation,
+                TokenKind::Number,
+                TokenKind::Punctuation,
+            ]
+        );
+    }
+
+    #[test]
+    fn split_statements_on_top_level_semicolons() {
+        let tokens = tokenize("SELECT ';'; -- only a comment\n; SELECT $$;$$;").unwrap();
+        let statements = split_statements(tokens);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(to_sql(&statements[1]).trim(), "SELECT $$;$$");
+    }
+}
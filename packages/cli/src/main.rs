@@ -1,22 +1,38 @@
 mod cli;
 mod codegen;
 mod config;
+mod config_command;
 mod data;
+mod dialects;
 mod error;
 mod generate_schema_command;
+mod lexer;
+mod migrate_command;
 mod plugins;
 mod utils;
 
 use crate::codegen::Codegen;
+use crate::config_command::ConfigCommand;
 
 fn main() {
+    let cli = cli::Cli::new();
+
+    // The config commands don't need the config file.
+    if let Some(cli::Command::Config { action }) = &cli.command {
+        ConfigCommand::run(action).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1);
+        });
+        return;
+    }
+
     // Initialize codegen with config.
-    let codegen = Codegen::new().unwrap_or_else(|error| {
-        eprintln!("{:#?}", error);
+    let codegen = Codegen::new(cli).unwrap_or_else(|error| {
+        eprintln!("{error}");
         std::process::exit(1);
     });
     codegen.run().unwrap_or_else(|error| {
-        eprintln!("{:#?}", error);
+        eprintln!("{error}");
         std::process::exit(1);
     });
 }
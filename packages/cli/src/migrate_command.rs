@@ -0,0 +1,298 @@
+use crate::codegen::Codegen;
+use crate::dialects::Connection;
+use crate::generate_schema_command::GenerateSchemaCommand;
+use crate::{data, error, utils};
+use convert_case::{Case, Casing};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HISTORY_TABLE_NAME: &str = "_sql_codegen_migrations";
+
+// Migration applied to the database, as recorded in the history table.
+struct AppliedMigration {
+    name: String,
+    checksum: String,
+    applied_at: String,
+}
+
+pub struct MigrateCommand {}
+
+impl MigrateCommand {
+    // Writes the migration turning the database into the schema used for the
+    // code generation.
+    pub fn diff(
+        codegen: &Codegen,
+        name: &str,
+        migrations_dir: &str,
+    ) -> Result<(), error::CodegenError> {
+        if !codegen.dialect.supports_migration_diff() {
+            return Err(error::CodegenError::ConfigError(format!(
+                "The \"migrate diff\" command doesn't support the \"{}\" dialect, write the migration by hand",
+                codegen.dialect.name()
+            )));
+        }
+        let live_database = data::Database::from_schema_ddl(
+            codegen.dialect.as_ref(),
+            &GenerateSchemaCommand::get_schema_ddl(codegen)?,
+        )?;
+        let database = codegen.get_database()?;
+        let diff = data::DatabaseDiff::new(codegen.dialect.as_ref(), &live_database, &database);
+        if diff.is_empty() {
+            println!("Database is up to date with the schema.");
+            return Ok(());
+        }
+        print!("{diff}");
+        if diff.is_destructive() {
+            println!("The migration contains destructive changes, review it before applying.");
+        }
+        let revert_diff =
+            data::DatabaseDiff::new(codegen.dialect.as_ref(), &database, &live_database);
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let file_name = format!(
+            "{}_{}.sql",
+            utils::format_utc_timestamp(seconds),
+            name.to_case(Case::Snake)
+        );
+        let migrations_dir_path = codegen.resolve_path(migrations_dir);
+        fs::create_dir_all(&migrations_dir_path)?;
+        let migration_file_path = migrations_dir_path.join(file_name);
+        fs::write(
+            &migration_file_path,
+            data::Migration::to_file_content(&diff.to_ddl(), &revert_diff.to_ddl()),
+        )?;
+        println!("Migration written to {}", migration_file_path.display());
+
+        Ok(())
+    }
+
+    // Applies the pending migrations in the order of their file names.
+    pub fn up(
+        codegen: &Codegen,
+        migrations_dir: &str,
+        regenerate_schema: bool,
+    ) -> Result<(), error::CodegenError> {
+        // The schema file is checked before the migrations are applied.
+        if regenerate_schema {
+            codegen.get_schema_file_path()?;
+        }
+        let migrations = MigrateCommand::get_migrations(codegen, migrations_dir)?;
+        let mut connection = codegen.connect()?;
+        let applied_migrations =
+            MigrateCommand::get_applied_migrations(codegen, &mut *connection, true)?;
+        MigrateCommand::verify_checksums(&migrations, &applied_migrations)?;
+
+        let pending_migrations = migrations
+            .iter()
+            .filter(|migration| {
+                !applied_migrations
+                    .iter()
+                    .any(|applied_migration| applied_migration.name == migration.name)
+            })
+            .collect::<Vec<&data::Migration>>();
+        if pending_migrations.is_empty() {
+            println!("No pending migrations.");
+        }
+        for migration in pending_migrations {
+            MigrateCommand::execute_in_transaction(
+                &mut *connection,
+                &migration.up,
+                &format!(
+                    "INSERT INTO {HISTORY_TABLE_NAME} (name, checksum) VALUES ({}, {})",
+                    codegen.dialect.get_placeholder(1),
+                    codegen.dialect.get_placeholder(2)
+                ),
+                &[&migration.name, &migration.checksum],
+            )?;
+            println!("Applied {}", migration.name);
+        }
+        connection.close()?;
+
+        if regenerate_schema {
+            GenerateSchemaCommand::run(codegen, true)?;
+        }
+        Ok(())
+    }
+
+    // Reverts the last applied migrations.
+    pub fn down(
+        codegen: &Codegen,
+        migrations_dir: &str,
+        steps: usize,
+        regenerate_schema: bool,
+    ) -> Result<(), error::CodegenError> {
+        // The schema file is checked before the migrations are applied.
+        if regenerate_schema {
+            codegen.get_schema_file_path()?;
+        }
+        let migrations = MigrateCommand::get_migrations(codegen, migrations_dir)?;
+        let mut connection = codegen.connect()?;
+        let applied_migrations =
+            MigrateCommand::get_applied_migrations(codegen, &mut *connection, true)?;
+        MigrateCommand::verify_checksums(&migrations, &applied_migrations)?;
+
+        if applied_migrations.is_empty() {
+            println!("No applied migrations.");
+        }
+        for applied_migration in applied_migrations.iter().rev().take(steps) {
+            let migration = migrations
+                .iter()
+                .find(|migration| migration.name == applied_migration.name)
+                .ok_or_else(|| {
+                    error::CodegenError::SchemaError(format!(
+                        "Migration file \"{}\" not found",
+                        applied_migration.name
+                    ))
+                })?;
+            let down = migration.down.as_ref().ok_or_else(|| {
+                error::CodegenError::SchemaError(format!(
+                    "Migration \"{}\" has no \"-- migrate:down\" section",
+                    migration.name
+                ))
+            })?;
+            MigrateCommand::execute_in_transaction(
+                &mut *connection,
+                down,
+                &format!(
+                    "DELETE FROM {HISTORY_TABLE_NAME} WHERE name = {}",
+                    codegen.dialect.get_placeholder(1)
+                ),
+                &[&migration.name],
+            )?;
+            println!("Reverted {}", migration.name);
+        }
+        connection.close()?;
+
+        if regenerate_schema {
+            GenerateSchemaCommand::run(codegen, true)?;
+        }
+        Ok(())
+    }
+
+    pub fn status(codegen: &Codegen, migrations_dir: &str) -> Result<(), error::CodegenError> {
+        let migrations = MigrateCommand::get_migrations(codegen, migrations_dir)?;
+        let mut connection = codegen.connect()?;
+        // The status doesn't create the history table, a missing table means
+        // that no migration is applied.
+        let applied_migrations =
+            MigrateCommand::get_applied_migrations(codegen, &mut *connection, false)?;
+        connection.close()?;
+
+        for migration in &migrations {
+            let applied_migration = applied_migrations
+                .iter()
+                .find(|applied_migration| applied_migration.name == migration.name);
+            let status = match applied_migration {
+                Some(applied_migration) if applied_migration.checksum != migration.checksum => {
+                    format!("edited after applied at {}", applied_migration.applied_at)
+                }
+                Some(applied_migration) => format!("applied at {}", applied_migration.applied_at),
+                None => "pending".to_string(),
+            };
+            println!("{} {status}", migration.name);
+        }
+        for applied_migration in &applied_migrations {
+            if !migrations
+                .iter()
+                .any(|migration| migration.name == applied_migration.name)
+            {
+                println!(
+                    "{} applied at {}, file not found",
+                    applied_migration.name, applied_migration.applied_at
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn get_migrations(
+        codegen: &Codegen,
+        migrations_dir: &str,
+    ) -> Result<Vec<data::Migration>, error::CodegenError> {
+        codegen
+            .get_migration_file_paths(&format!("{}/*.sql", migrations_dir.trim_end_matches('/')))?
+            .iter()
+            .map(data::Migration::from_file_path)
+            .collect()
+    }
+
+    // Creates the history table on the first run, when asked to.
+    fn get_applied_migrations(
+        codegen: &Codegen,
+        connection: &mut dyn Connection,
+        create_history_table: bool,
+    ) -> Result<Vec<AppliedMigration>, error::CodegenError> {
+        if create_history_table {
+            connection.batch_execute(
+                &codegen
+                    .dialect
+                    .get_migration_history_table_ddl(HISTORY_TABLE_NAME),
+            )?;
+        } else if connection
+            .query(&codegen.dialect.get_table_exists_sql(HISTORY_TABLE_NAME))?
+            .is_empty()
+        {
+            return Ok(vec![]);
+        }
+        let rows = connection.query(&format!(
+            "SELECT name, checksum, applied_at FROM {HISTORY_TABLE_NAME} ORDER BY name ASC"
+        ))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let mut values = row.into_iter().map(Option::unwrap_or_default);
+                AppliedMigration {
+                    name: values.next().unwrap_or_default(),
+                    checksum: values.next().unwrap_or_default(),
+                    applied_at: values.next().unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
+
+    // The migration is recorded in the history table only if it succeeds.
+    fn execute_in_transaction(
+        connection: &mut dyn Connection,
+        ddl: &str,
+        history_sql: &str,
+        history_params: &[&str],
+    ) -> Result<(), error::CodegenError> {
+        connection.begin_transaction()?;
+        let result = connection
+            .batch_execute(ddl)
+            .and_then(|_| connection.execute(history_sql, history_params));
+        match result {
+            Ok(()) => connection.commit_transaction(),
+            Err(error) => {
+                // The error of the migration matters more than the rollback one.
+                connection.rollback_transaction().ok();
+                Err(error)
+            }
+        }
+    }
+
+    // Applied migrations must not be edited, as the changes would never reach
+    // the databases they were already applied to.
+    fn verify_checksums(
+        migrations: &[data::Migration],
+        applied_migrations: &[AppliedMigration],
+    ) -> Result<(), error::CodegenError> {
+        for applied_migration in applied_migrations {
+            let migration = migrations
+                .iter()
+                .find(|migration| migration.name == applied_migration.name);
+            if let Some(migration) = migration {
+                if migration.checksum != applied_migration.checksum {
+                    return Err(error::CodegenError::SchemaError(format!(
+                        "Migration \"{}\" has been edited after it was applied",
+                        migration.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
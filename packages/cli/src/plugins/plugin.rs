@@ -1,8 +1,8 @@
-use crate::data;
+use crate::{data, error};
 
 use super::PluginResult;
 
 pub trait Plugin {
     fn name(&self) -> &'static str;
-    fn run(&self, data: &data::Data) -> PluginResult;
+    fn run(&self, data: &data::Data) -> Result<PluginResult, error::CodegenError>;
 }
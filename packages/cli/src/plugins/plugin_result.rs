@@ -28,7 +28,12 @@ impl PluginResult {
 
     pub fn append(&mut self, plugin_result: &mut PluginResult) {
         self.codes.append(&mut plugin_result.codes);
-        self.imports.append(&mut plugin_result.imports);
+        // Plugins can need the same imports.
+        for import in plugin_result.imports.drain(..) {
+            if !self.imports.contains(&import) {
+                self.imports.push(import);
+            }
+        }
         self.scalars.append(&mut plugin_result.scalars);
     }
 
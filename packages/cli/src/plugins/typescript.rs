@@ -1,105 +1,846 @@
 use super::{Plugin, PluginResult};
-use crate::data;
+use crate::dialects::Dialect;
+use crate::{config, data, error};
 use convert_case::{Case, Casing};
+use serde_json::{Map, Value};
+use sqlparser::ast::DataType;
+use std::collections::HashMap;
+
+// Scalar name and the TypeScript type of the scalar, the dialects decide which
+// scalars the SQL types are mapped to.
+const SCALARS: [(&str, &str); 53] = [
+    ("Boolean", "boolean"),
+    ("SmallInt", "number"),
+    ("Int", "number"),
+    ("BigInt", "BigInt"),
+    ("Real", "number"),
+    ("Double", "number"),
+    ("Numeric", "string"),
+    ("Money", "string"),
+    ("Char", "string"),
+    ("Text", "string"),
+    ("Uuid", "string"),
+    ("Bytea", "Buffer"),
+    ("Date", "Date"),
+    ("Time", "string"),
+    ("TimeTz", "string"),
+    ("Timestamp", "Date"),
+    ("TimestampTz", "Date"),
+    ("Interval", "string"),
+    ("Json", "Record<string, unknown>"),
+    ("JsonPath", "string"),
+    ("Hstore", "Record<string, unknown>"),
+    ("Xml", "string"),
+    ("Inet", "string"),
+    ("Cidr", "string"),
+    ("MacAddr", "string"),
+    ("Bit", "string"),
+    ("TsVector", "string"),
+    ("TsQuery", "string"),
+    ("Point", "{ x: number; y: number }"),
+    ("Circle", "{ x: number; y: number; radius: number }"),
+    ("Line", "string"),
+    ("Lseg", "string"),
+    ("Box", "string"),
+    ("Path", "string"),
+    ("Polygon", "string"),
+    ("Int4Range", "string"),
+    ("Int8Range", "string"),
+    ("NumRange", "string"),
+    ("TsRange", "string"),
+    ("TsTzRange", "string"),
+    ("DateRange", "string"),
+    ("Oid", "number"),
+    ("RegType", "string"),
+    ("PgLsn", "string"),
+    ("Integer", "number"),
+    ("Blob", "Buffer"),
+    ("Number", "number"),
+    ("TinyInt", "number"),
+    ("MediumInt", "number"),
+    ("DateTime", "Date"),
+    ("Year", "number"),
+    ("Geometry", "Record<string, unknown>"),
+    ("DateTimeOffset", "Date"),
+];
 
 #[derive(Debug)]
-pub struct TypeScriptPlugin {
+pub struct TypeScriptPlugin<'a> {
     name: &'static str,
+    dialect: &'a dyn Dialect,
+    scalar_configs: &'a HashMap<String, config::TypeConfig>,
+    override_configs: &'a HashMap<String, config::TypeConfig>,
 }
 
-impl TypeScriptPlugin {
-    pub fn new() -> TypeScriptPlugin {
-        TypeScriptPlugin { name: "typescript" }
+impl<'a> TypeScriptPlugin<'a> {
+    pub fn new(
+        dialect: &'a dyn Dialect,
+        scalar_configs: &'a HashMap<String, config::TypeConfig>,
+        override_configs: &'a HashMap<String, config::TypeConfig>,
+    ) -> TypeScriptPlugin<'a> {
+        TypeScriptPlugin {
+            name: "typescript",
+            dialect,
+            scalar_configs,
+            override_configs,
+        }
+    }
+
+    fn get_type_config_type_name(&self, type_config: &config::TypeConfig) -> String {
+        match type_config {
+            config::TypeConfig::Type(type_name) => type_name.clone(),
+            config::TypeConfig::ImportedType { type_name, .. } => type_name.clone(),
+            config::TypeConfig::JsonSchema { json_schema } => {
+                self.get_json_schema_type_name(json_schema)
+            }
+        }
+    }
+
+    // Only the leading identifier is imported, like `Prisma` for `Prisma.Decimal`.
+    fn get_type_config_import(&self, type_config: &config::TypeConfig) -> Option<String> {
+        match type_config {
+            config::TypeConfig::Type(_) | config::TypeConfig::JsonSchema { .. } => None,
+            config::TypeConfig::ImportedType { type_name, import } => {
+                let identifier = type_name
+                    .split(|character: char| !character.is_alphanumeric() && character != '_')
+                    .next()
+                    .unwrap_or_default();
+                Some(format!(
+                    "import type {{ {identifier} }} from {};",
+                    serde_json::to_string(import).unwrap()
+                ))
+            }
+        }
     }
 
     pub fn get_table_type_name(&self, table: &data::Table) -> String {
-        table.name.to_case(Case::Pascal)
-    }
-
-    pub fn get_column_field_type_name(&self, column: &data::Column) -> String {
-        let sql_type = column.sql_type.to_string().replace("[]", "");
-        let ts_type = match sql_type.as_str() {
-            "BOOLEAN" => "Scalars[\"Boolean\"]".to_string(),
-            "BIGINT" => "Scalars[\"BigInt\"]".to_string(),
-            "HSTORE" => "Scalars[\"Hstore\"]".to_string(),
-            "TEXT" => "Scalars[\"Text\"]".to_string(),
-            "UUID" => "Scalars[\"Uuid\"]".to_string(),
-            sql_type if sql_type.contains("CHAR") => "Scalars[\"Char\"]".to_string(),
-            sql_type if sql_type.contains("DOUBLE") => "Scalars[\"Double\"]".to_string(),
-            sql_type if sql_type.contains("ENUM") => "unknown".to_string(),
-            sql_type if sql_type.contains("INT") => "Scalars[\"Int\"]".to_string(),
-            sql_type if sql_type.contains("JSON") => "Scalars[\"Json\"]".to_string(),
-            sql_type if sql_type.contains("REAL") => "Scalars[\"Real\"]".to_string(),
-            sql_type if sql_type.contains("TIMESTAMP") => "Scalars[\"Timestamp\"]".to_string(),
-            _ => sql_type.to_string(),
+        match &table.alias {
+            Some(alias) => alias.clone(),
+            None => table.name.to_case(Case::Pascal),
+        }
+    }
+
+    pub fn get_enum_type_name(&self, enum_type: &data::EnumType) -> String {
+        enum_type.name.to_case(Case::Pascal)
+    }
+
+    // Scalars can also be added in the config for the types without the
+    // built-in mapping, like `"Citext": "string"` for the `citext` columns.
+    // The config scalars named after the SQL types come first, so the types
+    // the dialect maps to a broader scalar, like `DATETIME` in SQLite, can be
+    // typed.
+    fn get_scalar_name(&self, sql_type_name: &str) -> Option<String> {
+        self.scalar_configs
+            .keys()
+            .find(|scalar_name| scalar_name.eq_ignore_ascii_case(sql_type_name))
+            .cloned()
+            .or_else(|| {
+                self.dialect
+                    .get_scalar_name(sql_type_name)
+                    .map(|scalar_name| scalar_name.to_string())
+            })
+    }
+
+    pub fn get_column_field_type_name(
+        &self,
+        database: &data::Database,
+        table: &data::Table,
+        column: &data::Column,
+    ) -> Result<String, error::CodegenError> {
+        let mut sql_type = &column.sql_type;
+        let mut array_dimensions = column.array_dimensions;
+        while let DataType::Array(element_type) = sql_type {
+            sql_type = element_type;
+            array_dimensions += 1;
+        }
+        let sql_type_name = self.dialect.get_sql_type_name(sql_type);
+        let override_config = self
+            .override_configs
+            .get(&format!("{}.{}", table.name, column.name));
+        let ts_type = match (
+            override_config,
+            &column.type_annotation,
+            &column.allowed_values,
+        ) {
+            (Some(override_config), _, _) => self.get_type_config_type_name(override_config),
+            // The annotation is either the JSON Schema or the TypeScript type.
+            (None, Some(type_annotation), _) => match self.parse_json_schema(type_annotation) {
+                Some(json_schema) => self.get_json_schema_type_name(&json_schema),
+                None => type_annotation.clone(),
+            },
+            // Columns limited by the check constraint are typed as the literal union.
+            (None, None, Some(allowed_values)) => allowed_values
+                .iter()
+                .map(|value| serde_json::to_string(value).unwrap())
+                .collect::<Vec<String>>()
+                .join(" | "),
+            (None, None, None) => match (
+                self.get_scalar_name(&sql_type_name),
+                database.find_enum_type(&sql_type_name),
+            ) {
+                (Some(scalar_name), _) => format!("Scalars[\"{scalar_name}\"]"),
+                (None, Some(enum_type)) => self.get_enum_type_name(enum_type),
+                (None, None) => {
+                    return Err(error::CodegenError::SchemaError(format!(
+                        "Unsupported type \"{sql_type_name}\" of column \"{}\".\"{}\", map it in the \"scalars\" config",
+                        table.name, column.name
+                    )))
+                }
+            },
         };
-        format!(
+        // Elements of the array can be null regardless of the column constraints.
+        let ts_type = (0..array_dimensions).fold(ts_type, |ts_type, dimension| {
+            if dimension == 0 {
+                format!("({ts_type} | null)[]")
+            } else {
+                format!("{ts_type}[]")
+            }
+        });
+        Ok(format!(
             "{ts_type}{or_null}",
             ts_type = ts_type,
             or_null = if column.is_not_null { "" } else { " | null" }
-        )
+        ))
+    }
+
+    // The nested values are typed from the JSON Schema of the column, the
+    // values with unknown schema fall back to the JSON and text scalars.
+    pub fn get_selection_field_type_name(
+        &self,
+        database: &data::Database,
+        selection: &data::Selection,
+    ) -> Result<String, error::CodegenError> {
+        let json_access = match &selection.json_access {
+            Some(json_access) => json_access,
+            None => {
+                return self.get_column_field_type_name(database, selection.table, selection.column)
+            }
+        };
+        let mut json_schema = self.get_column_json_schema(selection.table, selection.column);
+        // The missing keys and the null column give null.
+        let mut is_not_null = selection.column.is_not_null && json_schema.is_some();
+        for key in &json_access.path {
+            let property = json_schema
+                .as_ref()
+                .and_then(|json_schema| self.get_json_schema_property(json_schema, key));
+            is_not_null = is_not_null && matches!(property, Some((_, true)));
+            json_schema = property.map(|(json_schema, _)| json_schema);
+        }
+        let ts_type = match json_schema {
+            Some(json_schema)
+                if !json_access.is_text || self.is_json_schema_string(&json_schema) =>
+            {
+                self.get_json_schema_type_name(&json_schema)
+            }
+            _ if json_access.is_text => "Scalars[\"Text\"]".to_string(),
+            _ => "Scalars[\"Json\"]".to_string(),
+        };
+        Ok(format!(
+            "{ts_type}{or_null}",
+            or_null = if is_not_null { "" } else { " | null" }
+        ))
+    }
+
+    fn parse_json_schema(&self, text: &str) -> Option<Value> {
+        serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(|json_schema| json_schema.is_object())
+    }
+
+    fn get_column_json_schema(&self, table: &data::Table, column: &data::Column) -> Option<Value> {
+        match self
+            .override_configs
+            .get(&format!("{}.{}", table.name, column.name))
+        {
+            Some(config::TypeConfig::JsonSchema { json_schema }) => Some(json_schema.clone()),
+            Some(_) => None,
+            None => column
+                .type_annotation
+                .as_ref()
+                .and_then(|type_annotation| self.parse_json_schema(type_annotation)),
+        }
+    }
+
+    // Returns the schema of the property or the array item together with
+    // whether it is required.
+    fn get_json_schema_property(&self, json_schema: &Value, key: &str) -> Option<(Value, bool)> {
+        let object = json_schema.as_object()?;
+        if let Some(property) = object
+            .get("properties")
+            .and_then(|properties| properties.get(key))
+        {
+            return Some((
+                property.clone(),
+                self.is_json_schema_property_required(object, key),
+            ));
+        }
+        if key.parse::<usize>().is_ok() {
+            if let Some(items) = object.get("items") {
+                return Some((items.clone(), false));
+            }
+        }
+        match object.get("additionalProperties") {
+            Some(property @ Value::Object(_)) => Some((property.clone(), false)),
+            _ => None,
+        }
+    }
+
+    fn is_json_schema_property_required(&self, object: &Map<String, Value>, key: &str) -> bool {
+        match object.get("required") {
+            Some(Value::Array(required)) => required.iter().any(|name| name.as_str() == Some(key)),
+            _ => false,
+        }
+    }
+
+    // The `->>` operator returns the strings as they are.
+    fn is_json_schema_string(&self, json_schema: &Value) -> bool {
+        match json_schema.get("enum").or_else(|| json_schema.get("const")) {
+            Some(Value::Array(values)) => values.iter().all(|value| value.is_string()),
+            Some(value) => value.is_string(),
+            None => {
+                json_schema
+                    .get("type")
+                    .and_then(|type_name| type_name.as_str())
+                    == Some("string")
+            }
+        }
+    }
+
+    // Unsupported keywords, like `$ref`, give the `unknown` type.
+    fn get_json_schema_type_name(&self, json_schema: &Value) -> String {
+        let object = match json_schema {
+            Value::Object(object) => object,
+            Value::Bool(false) => return "never".to_string(),
+            _ => return "unknown".to_string(),
+        };
+        if let Some(value) = object.get("const") {
+            return serde_json::to_string(value).unwrap();
+        }
+        if let Some(Value::Array(values)) = object.get("enum") {
+            return values
+                .iter()
+                .map(|value| serde_json::to_string(value).unwrap())
+                .collect::<Vec<String>>()
+                .join(" | ");
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(json_schemas)) = object.get(keyword) {
+                return json_schemas
+                    .iter()
+                    .map(|json_schema| self.get_json_schema_type_name(json_schema))
+                    .collect::<Vec<String>>()
+                    .join(" | ");
+            }
+        }
+        let type_names = match object.get("type") {
+            Some(Value::String(type_name)) => vec![type_name.as_str()],
+            Some(Value::Array(type_names)) => type_names
+                .iter()
+                .filter_map(|type_name| type_name.as_str())
+                .collect(),
+            _ if object.contains_key("properties") => vec!["object"],
+            _ => return "unknown".to_string(),
+        };
+        type_names
+            .iter()
+            .map(|type_name| match *type_name {
+                "string" => "string".to_string(),
+                "number" | "integer" => "number".to_string(),
+                "boolean" => "boolean".to_string(),
+                "null" => "null".to_string(),
+                "array" => format!(
+                    "Array<{}>",
+                    object
+                        .get("items")
+                        .map(|items| self.get_json_schema_type_name(items))
+                        .unwrap_or_else(|| "unknown".to_string())
+                ),
+                "object" => self.get_json_schema_object_type_name(object),
+                _ => "unknown".to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+
+    fn get_json_schema_object_type_name(&self, object: &Map<String, Value>) -> String {
+        let properties = match object.get("properties") {
+            Some(Value::Object(properties)) if !properties.is_empty() => properties,
+            _ => {
+                let value_type_name = match object.get("additionalProperties") {
+                    Some(json_schema @ Value::Object(_)) => {
+                        self.get_json_schema_type_name(json_schema)
+                    }
+                    _ => "unknown".to_string(),
+                };
+                return format!("Record<string, {value_type_name}>");
+            }
+        };
+        let fields = properties
+            .iter()
+            .map(|(key, property)| {
+                let is_identifier = key.chars().enumerate().all(|(index, character)| {
+                    character.is_ascii_alphabetic()
+                        || character == '_'
+                        || character == '$'
+                        || (index > 0 && character.is_ascii_digit())
+                });
+                let name = if is_identifier && !key.is_empty() {
+                    key.clone()
+                } else {
+                    serde_json::to_string(key).unwrap()
+                };
+                let optional = if self.is_json_schema_property_required(object, key) {
+                    ""
+                } else {
+                    "?"
+                };
+                format!(
+                    "{name}{optional}: {}",
+                    self.get_json_schema_type_name(property)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        format!("{{ {fields} }}")
     }
 
     pub fn get_column_field_name(&self, column: &data::Column) -> String {
-        column.name.clone()
+        match &column.alias {
+            Some(alias) => alias.clone(),
+            None => column.name.clone(),
+        }
+    }
+
+    // Returns the JSDoc comment with the trailing line break, or an empty
+    // string when there is no description.
+    pub fn get_doc_comment(&self, description: &Option<String>, indentation: &str) -> String {
+        let description = match description {
+            Some(description) if !description.trim().is_empty() => description,
+            _ => return String::from(""),
+        };
+        let lines = description
+            .trim()
+            .replace("*/", "*\\/")
+            .lines()
+            .map(|line| format!("{indentation} * {line}").trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        format!("{indentation}/**\n{lines}\n{indentation} */\n")
+    }
+
+    fn get_enum_type_definition(&self, enum_type: &data::EnumType) -> String {
+        let name = self.get_enum_type_name(enum_type);
+        let values = enum_type
+            .values
+            .iter()
+            .map(|value| serde_json::to_string(value).unwrap())
+            .collect::<Vec<String>>()
+            .join(" | ");
+        format!("export type {name} = {values};")
     }
 
-    fn get_table_type_definition(&self, table: &data::Table) -> String {
+    fn get_table_type_definition(
+        &self,
+        database: &data::Database,
+        table: &data::Table,
+    ) -> Result<String, error::CodegenError> {
         let name = self.get_table_type_name(table);
         let fields = table
             .columns
             .iter()
-            .map(|column| self.get_column_field_definition(column))
-            .collect::<Vec<String>>()
+            .map(|column| self.get_column_field_definition(database, table, column))
+            .collect::<Result<Vec<String>, error::CodegenError>>()?
             .join("\n");
-        format!(
+        let doc_comment = self.get_doc_comment(&table.description, "");
+        Ok(format!(
             "\
+            {doc_comment}\
             export type {name} = {{\n\
             {fields}\n\
             }};"
-        )
+        ))
     }
 
-    fn get_column_field_definition(&self, column: &data::Column) -> String {
+    fn get_column_field_definition(
+        &self,
+        database: &data::Database,
+        table: &data::Table,
+        column: &data::Column,
+    ) -> Result<String, error::CodegenError> {
         let name = self.get_column_field_name(column);
-        let ts_type = self.get_column_field_type_name(column);
-        format!("\t{name}: {ts_type};")
+        let ts_type = self.get_column_field_type_name(database, table, column)?;
+        let doc_comment = self.get_doc_comment(&column.description, "\t");
+        Ok(format!("{doc_comment}\t{name}: {ts_type};"))
+    }
+
+    pub fn get_table_insert_input_type_name(&self, table: &data::Table) -> String {
+        format!("{}InsertInput", self.get_table_type_name(table))
     }
 
+    fn get_table_insert_input_type_definition(
+        &self,
+        database: &data::Database,
+        table: &data::Table,
+    ) -> Result<String, error::CodegenError> {
+        let name = self.get_table_insert_input_type_name(table);
+        let fields = table
+            .columns
+            .iter()
+            .filter(|column| column.is_insertable())
+            .map(|column| self.get_column_insert_input_field_definition(database, table, column))
+            .collect::<Result<Vec<String>, error::CodegenError>>()?
+            .join("\n");
+        Ok(format!(
+            "\
+            export type {name} = {{\n\
+            {fields}\n\
+            }};"
+        ))
+    }
+
+    fn get_column_insert_input_field_definition(
+        &self,
+        database: &data::Database,
+        table: &data::Table,
+        column: &data::Column,
+    ) -> Result<String, error::CodegenError> {
+        let name = self.get_column_field_name(column);
+        let ts_type = self.get_column_field_type_name(database, table, column)?;
+        let optional = if column.is_optional_on_insert() {
+            "?"
+        } else {
+            ""
+        };
+        let doc_comment = self.get_doc_comment(&column.description, "\t");
+        Ok(format!("{doc_comment}\t{name}{optional}: {ts_type};"))
+    }
+
+    // The built-in scalars come first, followed by the ones added in the config.
     fn get_scalars(&self) -> Vec<(String, String)> {
-        vec![
-            ("BigInt".to_string(), "BigInt".to_string()),
-            ("Boolean".to_string(), "boolean".to_string()),
-            ("Char".to_string(), "string".to_string()),
-            ("Double".to_string(), "number".to_string()),
-            ("Hstore".to_string(), "Record<string, unknown>".to_string()),
-            ("Int".to_string(), "number".to_string()),
-            ("Json".to_string(), "Record<string, unknown>".to_string()),
-            ("Real".to_string(), "number".to_string()),
-            ("Text".to_string(), "string".to_string()),
-            ("Timestamp".to_string(), "Date".to_string()),
-            ("Uuid".to_string(), "string".to_string()),
-        ]
-    }
-
-    fn get_codes(&self, tables: &Vec<data::Table>) -> Vec<String> {
-        tables
+        let scalar_names = self.dialect.get_scalar_names();
+        let mut scalars = scalar_names
             .iter()
-            .map(|table| self.get_table_type_definition(table))
-            .collect::<Vec<String>>()
+            .map(|scalar_name| {
+                let ts_type = match self.scalar_configs.get(*scalar_name) {
+                    Some(scalar_config) => self.get_type_config_type_name(scalar_config),
+                    None => SCALARS
+                        .iter()
+                        .find(|(other_scalar_name, _)| other_scalar_name == scalar_name)
+                        .map(|(_, ts_type)| ts_type.to_string())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                };
+                (scalar_name.to_string(), ts_type)
+            })
+            .collect::<Vec<(String, String)>>();
+        let mut added_scalars = self
+            .scalar_configs
+            .iter()
+            .filter(|(scalar_name, _)| !scalar_names.contains(&scalar_name.as_str()))
+            .map(|(scalar_name, scalar_config)| {
+                (
+                    scalar_name.clone(),
+                    self.get_type_config_type_name(scalar_config),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+        added_scalars.sort();
+        scalars.append(&mut added_scalars);
+        scalars
+    }
+
+    fn get_imports(&self) -> Vec<String> {
+        let mut imports = self
+            .scalar_configs
+            .values()
+            .chain(self.override_configs.values())
+            .filter_map(|type_config| self.get_type_config_import(type_config))
+            .collect::<Vec<String>>();
+        imports.sort();
+        imports
+    }
+
+    fn get_codes(&self, database: &data::Database) -> Result<Vec<String>, error::CodegenError> {
+        let mut codes = database
+            .enum_types
+            .iter()
+            .map(|enum_type| self.get_enum_type_definition(enum_type))
+            .collect::<Vec<String>>();
+        for table in &database.tables {
+            codes.push(self.get_table_type_definition(database, table)?);
+            codes.push(self.get_table_insert_input_type_definition(database, table)?);
+        }
+        Ok(codes)
     }
 }
 
-impl Plugin for TypeScriptPlugin {
+impl<'a> Plugin for TypeScriptPlugin<'a> {
     fn name(&self) -> &'static str {
         self.name
     }
 
-    fn run(&self, data: &data::Data) -> PluginResult {
-        PluginResult::from(
-            self.get_codes(&data.database.tables),
-            vec![],
+    fn run(&self, data: &data::Data) -> Result<PluginResult, error::CodegenError> {
+        Ok(PluginResult::from(
+            self.get_codes(data.database)?,
+            self.get_imports(),
             self.get_scalars(),
-        )
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::PostgresDialect;
+    use serde_json::json;
+    use sqlparser::ast::{Ident, ObjectName};
+
+    fn create_settings_table(is_not_null: bool) -> data::Table {
+        let mut column = data::Column::new(
+            "settings".to_string(),
+            DataType::Custom(ObjectName(vec![Ident::new("jsonb")])),
+            false,
+            false,
+            is_not_null,
+            None,
+        );
+        column.type_annotation = Some(
+            json!({
+                "type": "object",
+                "properties": {
+                    "theme": { "enum": ["dark", "light"] },
+                    "fontSize": { "type": "integer" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["theme", "fontSize"]
+            })
+            .to_string(),
+        );
+        data::Table::new("users".to_string(), vec![column])
+    }
+
+    #[test]
+    fn get_scalar_and_override_type_names() {
+        let dialect = PostgresDialect::new();
+        let scalar_configs = HashMap::from([
+            (
+                "Numeric".to_string(),
+                config::TypeConfig::ImportedType {
+                    type_name: "Prisma.Decimal".to_string(),
+                    import: "@prisma/client".to_string(),
+                },
+            ),
+            (
+                "Citext".to_string(),
+                config::TypeConfig::Type("string".to_string()),
+            ),
+        ]);
+        let override_configs = HashMap::from([
+            (
+                "users.role".to_string(),
+                config::TypeConfig::Type("Role".to_string()),
+            ),
+            (
+                "users.mood".to_string(),
+                config::TypeConfig::Type("UserMood".to_string()),
+            ),
+        ]);
+        let plugin = TypeScriptPlugin::new(&dialect, &scalar_configs, &override_configs);
+        let column = |name: &str, sql_type: DataType, allowed_values: Option<&[&str]>| {
+            let mut column =
+                data::Column::new(name.to_string(), sql_type, false, false, true, None);
+            column.allowed_values =
+                allowed_values.map(|values| values.iter().map(|value| value.to_string()).collect());
+            column
+        };
+        let custom_type = |name: &str| DataType::Custom(ObjectName(vec![Ident::new(name)]));
+        let mut database = data::Database::new(
+            "public".to_string(),
+            vec![data::Table::new(
+                "users".to_string(),
+                vec![
+                    column("balance", DataType::Decimal(None, None), None),
+                    column("email", custom_type("citext"), None),
+                    column("role", DataType::Text, Some(&["admin", "member"])),
+                    column("kind", DataType::Text, Some(&["person", "bot"])),
+                    column("mood", custom_type("mood"), None),
+                    column("previous_mood", custom_type("mood"), None),
+                ],
+            )],
+        );
+        database.enum_types.push(data::EnumType::new(
+            "mood".to_string(),
+            vec!["happy".to_string(), "sad".to_string()],
+        ));
+        let table = &database.tables[0];
+        let type_names = table
+            .columns
+            .iter()
+            .map(|column| {
+                plugin
+                    .get_column_field_type_name(&database, table, column)
+                    .unwrap()
+            })
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            type_names,
+            [
+                "Scalars[\"Numeric\"]",
+                "Scalars[\"Citext\"]",
+                "Role",
+                "\"person\" | \"bot\"",
+                "UserMood",
+                "Mood",
+            ]
+        );
+        assert_eq!(plugin.get_scalar_name("CITEXT"), Some("Citext".to_string()));
+        assert_eq!(plugin.get_scalar_name("int4"), Some("Int".to_string()));
+        assert_eq!(plugin.get_scalar_name("mood"), None);
+
+        let scalars = plugin.get_scalars();
+        assert_eq!(scalars[0], ("Boolean".to_string(), "boolean".to_string()));
+        assert_eq!(
+            scalars
+                .iter()
+                .filter(|(scalar_name, _)| scalar_name == "Numeric")
+                .collect::<Vec<&(String, String)>>(),
+            [&("Numeric".to_string(), "Prisma.Decimal".to_string())]
+        );
+        assert_eq!(
+            scalars.last(),
+            Some(&("Citext".to_string(), "string".to_string()))
+        );
+        assert_eq!(
+            plugin.get_imports(),
+            ["import type { Prisma } from \"@prisma/client\";"]
+        );
+        assert_eq!(
+            plugin.get_type_config_import(&config::TypeConfig::Type("string".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn get_json_schema_type_names() {
+        let dialect = PostgresDialect::new();
+        let configs = HashMap::new();
+        let plugin = TypeScriptPlugin::new(&dialect, &configs, &configs);
+        let type_name = |json_schema: Value| plugin.get_json_schema_type_name(&json_schema);
+
+        assert_eq!(
+            type_name(json!({
+                "type": "object",
+                "properties": {
+                    "theme": { "enum": ["dark", "light"] },
+                    "font-size": { "type": "integer" }
+                },
+                "required": ["theme"]
+            })),
+            "{ \"font-size\"?: number; theme: \"dark\" | \"light\" }"
+        );
+        assert_eq!(type_name(json!({ "const": 1 })), "1");
+        assert_eq!(
+            type_name(json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })),
+            "string | null"
+        );
+        assert_eq!(
+            type_name(json!({ "type": ["boolean", "null"] })),
+            "boolean | null"
+        );
+        assert_eq!(
+            type_name(json!({ "type": "array", "items": { "type": "number" } })),
+            "Array<number>"
+        );
+        assert_eq!(
+            type_name(json!({ "type": "object", "additionalProperties": { "type": "boolean" } })),
+            "Record<string, boolean>"
+        );
+        assert_eq!(
+            type_name(json!({ "type": "object" })),
+            "Record<string, unknown>"
+        );
+        assert_eq!(
+            type_name(json!({ "$ref": "#/definitions/theme" })),
+            "unknown"
+        );
+        assert_eq!(type_name(json!(false)), "never");
+    }
+
+    #[test]
+    fn get_json_schema_properties() {
+        let dialect = PostgresDialect::new();
+        let configs = HashMap::new();
+        let plugin = TypeScriptPlugin::new(&dialect, &configs, &configs);
+        let json_schema = json!({
+            "properties": { "theme": { "type": "string" }, "fontSize": { "type": "integer" } },
+            "required": ["theme"],
+            "items": { "type": "number" },
+            "additionalProperties": { "type": "boolean" }
+        });
+        let property = |key: &str| plugin.get_json_schema_property(&json_schema, key);
+
+        assert_eq!(property("theme"), Some((json!({ "type": "string" }), true)));
+        assert_eq!(
+            property("fontSize"),
+            Some((json!({ "type": "integer" }), false))
+        );
+        assert_eq!(property("0"), Some((json!({ "type": "number" }), false)));
+        assert_eq!(
+            property("other"),
+            Some((json!({ "type": "boolean" }), false))
+        );
+        assert_eq!(
+            plugin.get_json_schema_property(&json!({ "properties": {} }), "theme"),
+            None
+        );
+    }
+
+    #[test]
+    fn get_json_access_field_type_names() {
+        let dialect = PostgresDialect::new();
+        let configs = HashMap::new();
+        let plugin = TypeScriptPlugin::new(&dialect, &configs, &configs);
+        let mut guests_table = create_settings_table(false);
+        guests_table.name = "guests".to_string();
+        let database = data::Database::new(
+            "public".to_string(),
+            vec![create_settings_table(true), guests_table],
+        );
+        let type_name = |table_index: usize, path: &[&str], is_text: bool| {
+            let table = &database.tables[table_index];
+            let mut selection = data::Selection::new(
+                &database,
+                table.name.clone(),
+                table,
+                "settings".to_string(),
+                &table.columns[0],
+            );
+            selection.json_access = Some(data::JsonAccess::new(
+                path.iter().map(|key| key.to_string()).collect(),
+                is_text,
+            ));
+            plugin
+                .get_selection_field_type_name(&database, &selection)
+                .unwrap()
+        };
+
+        assert_eq!(type_name(0, &["theme"], true), "\"dark\" | \"light\"");
+        assert_eq!(type_name(0, &["fontSize"], false), "number");
+        // The `->>` operator returns the numbers as text.
+        assert_eq!(type_name(0, &["fontSize"], true), "Scalars[\"Text\"]");
+        assert_eq!(type_name(0, &["tags", "0"], true), "string | null");
+        assert_eq!(type_name(0, &["tags"], false), "Array<string> | null");
+        assert_eq!(
+            type_name(0, &["missing"], false),
+            "Scalars[\"Json\"] | null"
+        );
+        assert_eq!(
+            type_name(0, &["missing", "nested"], true),
+            "Scalars[\"Text\"] | null"
+        );
+        assert_eq!(
+            type_name(1, &["theme"], true),
+            "\"dark\" | \"light\" | null"
+        );
     }
 }
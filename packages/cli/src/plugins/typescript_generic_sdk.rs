@@ -1,5 +1,5 @@
 use super::{Plugin, PluginResult, TypeScriptOperationsPlugin};
-use crate::data;
+use crate::{data, error};
 use convert_case::{Case, Casing};
 
 #[derive(Debug)]
@@ -79,8 +79,27 @@ impl<'a> TypeScriptGenericSdkPlugin<'a> {
         let document_variable_name = self
             .typescript_operation_plugin
             .get_ddl_variable_name(query);
+        let renamed_fields = self
+            .typescript_operation_plugin
+            .get_object_result_renamed_fields(query);
+        let object_result_arguments = if renamed_fields.is_empty() {
+            document_variable_name.clone()
+        } else {
+            let keys = renamed_fields
+                .iter()
+                .map(|(column_name, field_name)| {
+                    format!(
+                        "{}: {}",
+                        serde_json::to_string(column_name).unwrap(),
+                        serde_json::to_string(field_name).unwrap()
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("{document_variable_name}, {{ {keys} }}")
+        };
         format!(
-            "\t\t{function_name}: fetchObjectResult<{object_result_type_name}, {variables_type_name}>({document_variable_name}),\n\
+            "\t\t{function_name}: fetchObjectResult<{object_result_type_name}, {variables_type_name}>({object_result_arguments}),\n\
             \t\t{function_name}AsArray: fetchArrayResult<{array_result_type_name}, {variables_type_name}>({document_variable_name}),"
         )
     }
@@ -89,11 +108,16 @@ impl<'a> TypeScriptGenericSdkPlugin<'a> {
         "const fetchArrayResult = <R, V>(query: string) => (params?: FetchArrayResultParams<V>): Promise<R[]> => requester<R[], V>({ ...params, query, rowMode: \"array\" });".to_string()
     }
 
+    fn get_rename_keys_definition(&self) -> String {
+        "const renameKeys = (row: Record<string, unknown>, keys: Record<string, string>) => Object.fromEntries(Object.entries(row).map(([key, value]) => [keys[key] ?? key, value]));".to_string()
+    }
+
     fn get_fetch_object_result_definition(&self) -> String {
-        "const fetchObjectResult = <R, V>(query: string) => (params?: FetchObjectResultParams<V>): Promise<R[]> => requester<R[], V>({ ...params, query, rowMode: \"object\" });".to_string()
+        "const fetchObjectResult = <R, V>(query: string, keys?: Record<string, string>) => async (params?: FetchObjectResultParams<V>): Promise<R[]> => (await requester<Record<string, unknown>[], V>({ ...params, query, rowMode: \"object\" })).map((row) => (keys ? renameKeys(row, keys) : row) as unknown as R);".to_string()
     }
 
     fn get_get_sdk_definition(&self, queries: &Vec<data::Query>) -> String {
+        let rename_keys = self.get_rename_keys_definition();
         let fetch_array_result = self.get_fetch_array_result_definition();
         let fetch_object_result = self.get_fetch_object_result_definition();
         let functions = queries
@@ -104,6 +128,7 @@ impl<'a> TypeScriptGenericSdkPlugin<'a> {
         format!(
             "\
             export const getSdk = (requester: Requester) => {{\n\
+            \t{rename_keys}\n\
             \t{fetch_array_result}\n\
             \t{fetch_object_result}\n\
             \n\
@@ -131,7 +156,7 @@ impl<'a> Plugin for TypeScriptGenericSdkPlugin<'a> {
         self.name
     }
 
-    fn run(&self, data: &data::Data) -> PluginResult {
-        PluginResult::from(self.get_codes(data), vec![], vec![])
+    fn run(&self, data: &data::Data) -> Result<PluginResult, error::CodegenError> {
+        Ok(PluginResult::from(self.get_codes(data), vec![], vec![]))
     }
 }
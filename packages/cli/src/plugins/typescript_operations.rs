@@ -1,44 +1,81 @@
 use super::PluginResult;
 use super::{Plugin, TypeScriptPlugin};
-use crate::data;
+use crate::{data, error};
 use convert_case::{Case, Casing};
 
 #[derive(Debug)]
 pub struct TypeScriptOperationsPlugin<'a> {
     name: &'static str,
-    typescript_plugin: &'a TypeScriptPlugin,
+    typescript_plugin: &'a TypeScriptPlugin<'a>,
 }
 
 impl<'a> TypeScriptOperationsPlugin<'a> {
-    pub fn new(typescript_plugin: &TypeScriptPlugin) -> TypeScriptOperationsPlugin {
+    pub fn new(typescript_plugin: &'a TypeScriptPlugin<'a>) -> TypeScriptOperationsPlugin<'a> {
         TypeScriptOperationsPlugin {
             name: "typescript-operations",
             typescript_plugin,
         }
     }
 
+    // The schema alias is used unless the column is renamed in the query.
+    fn get_selection_field_name(&self, selection: &data::Selection) -> String {
+        if selection.column_name == selection.column.name {
+            self.typescript_plugin
+                .get_column_field_name(selection.column)
+        } else {
+            selection.column_name.clone()
+        }
+    }
+
     pub fn get_array_result_element_name(&self, selection: &data::Selection) -> String {
-        selection.column_name.clone()
+        self.get_selection_field_name(selection)
     }
 
-    pub fn get_array_result_element_definition(&self, selection: &data::Selection) -> String {
+    pub fn get_array_result_element_definition(
+        &self,
+        database: &data::Database,
+        selection: &data::Selection,
+    ) -> Result<String, error::CodegenError> {
         let name = self.get_array_result_element_name(selection);
         let ts_type = self
             .typescript_plugin
-            .get_column_field_type_name(selection.column);
-        format!("\t{name}: {ts_type},")
+            .get_selection_field_type_name(database, selection)?;
+        let doc_comment = self
+            .typescript_plugin
+            .get_doc_comment(&selection.column.description, "\t");
+        Ok(format!("{doc_comment}\t{name}: {ts_type},"))
     }
 
     pub fn get_object_result_field_name(&self, selection: &data::Selection) -> String {
-        selection.column_name.clone()
+        self.get_selection_field_name(selection)
+    }
+
+    // Returns the row keys which have to be renamed to match the result type.
+    pub fn get_object_result_renamed_fields(&self, query: &data::Query) -> Vec<(String, String)> {
+        let mut renamed_fields: Vec<(String, String)> = vec![];
+        for selection in &query.projection.selections {
+            let field_name = self.get_object_result_field_name(selection);
+            renamed_fields.retain(|(column_name, _)| *column_name != selection.column_name);
+            if field_name != selection.column_name {
+                renamed_fields.push((selection.column_name.clone(), field_name));
+            }
+        }
+        renamed_fields
     }
 
-    pub fn get_object_result_field_definition(&self, selection: &data::Selection) -> String {
+    pub fn get_object_result_field_definition(
+        &self,
+        database: &data::Database,
+        selection: &data::Selection,
+    ) -> Result<String, error::CodegenError> {
         let name = self.get_object_result_field_name(selection);
         let ts_type = self
             .typescript_plugin
-            .get_column_field_type_name(selection.column);
-        format!("\t{name}: {ts_type};")
+            .get_selection_field_type_name(database, selection)?;
+        let doc_comment = self
+            .typescript_plugin
+            .get_doc_comment(&selection.column.description, "\t");
+        Ok(format!("{doc_comment}\t{name}: {ts_type};"))
     }
 
     fn get_file_stem(&self, query: &data::Query) -> String {
@@ -63,19 +100,29 @@ impl<'a> TypeScriptOperationsPlugin<'a> {
         format!("{prefix}QueryObjectResult")
     }
 
-    pub fn get_query_array_result_type_definition(&self, query: &data::Query) -> String {
+    pub fn get_query_array_result_type_definition(
+        &self,
+        query: &data::Query,
+    ) -> Result<String, error::CodegenError> {
         let array_result_type_name = self.get_query_array_result_type_name(query);
         let fields = query
             .projection
             .selections
             .iter()
-            .map(|selection| self.get_array_result_element_definition(selection))
-            .collect::<Vec<String>>()
+            .map(|selection| {
+                self.get_array_result_element_definition(query.projection.database, selection)
+            })
+            .collect::<Result<Vec<String>, error::CodegenError>>()?
             .join("\n");
-        format!("export type {array_result_type_name} = readonly [\n{fields}\n];")
+        Ok(format!(
+            "export type {array_result_type_name} = readonly [\n{fields}\n];"
+        ))
     }
 
-    pub fn get_query_object_result_type_definition(&self, query: &data::Query) -> String {
+    pub fn get_query_object_result_type_definition(
+        &self,
+        query: &data::Query,
+    ) -> Result<String, error::CodegenError> {
         let object_result_type_name = self.get_query_object_result_type_name(query);
         let fields = query
             .projection
@@ -92,15 +139,17 @@ impl<'a> TypeScriptOperationsPlugin<'a> {
                     .any(|selection_b| selection_a.column_name == selection_b.column_name);
                 !has_duplicate
             })
-            .map(|(_index, selection)| self.get_object_result_field_definition(selection))
-            .collect::<Vec<String>>()
+            .map(|(_index, selection)| {
+                self.get_object_result_field_definition(query.projection.database, selection)
+            })
+            .collect::<Result<Vec<String>, error::CodegenError>>()?
             .join("\n");
-        format!(
+        Ok(format!(
             "\
             export type {object_result_type_name} = Readonly<{{\n\
             {fields}\n\
             }}>;"
-        )
+        ))
     }
 
     pub fn get_variables_type_name(&self, query: &data::Query) -> String {
@@ -136,21 +185,24 @@ impl<'a> TypeScriptOperationsPlugin<'a> {
         )
     }
 
-    pub fn get_type_definitions(&self, query: &data::Query) -> Vec<String> {
-        vec![
-            self.get_query_array_result_type_definition(query),
-            self.get_query_object_result_type_definition(query),
+    pub fn get_type_definitions(
+        &self,
+        query: &data::Query,
+    ) -> Result<Vec<String>, error::CodegenError> {
+        Ok(vec![
+            self.get_query_array_result_type_definition(query)?,
+            self.get_query_object_result_type_definition(query)?,
             self.get_variables_type_definition(query),
             self.get_ddl_variable(query),
-        ]
+        ])
     }
 
-    pub fn get_codes(&self, data: &data::Data) -> Vec<String> {
-        data.queries
-            .iter()
-            .map(|query| self.get_type_definitions(query))
-            .flatten()
-            .collect::<Vec<String>>()
+    pub fn get_codes(&self, data: &data::Data) -> Result<Vec<String>, error::CodegenError> {
+        let mut codes = vec![];
+        for query in data.queries {
+            codes.append(&mut self.get_type_definitions(query)?);
+        }
+        Ok(codes)
     }
 }
 
@@ -159,7 +211,7 @@ impl<'a> Plugin for TypeScriptOperationsPlugin<'a> {
         self.name
     }
 
-    fn run(&self, data: &data::Data) -> PluginResult {
-        PluginResult::from(self.get_codes(data), vec![], vec![])
+    fn run(&self, data: &data::Data) -> Result<PluginResult, error::CodegenError> {
+        Ok(PluginResult::from(self.get_codes(data)?, vec![], vec![]))
     }
 }
@@ -1,5 +1,5 @@
 use super::{Plugin, PluginResult};
-use crate::data;
+use crate::{data, error};
 
 #[derive(Debug)]
 pub struct TypeScriptPgPlugin {
@@ -27,7 +27,11 @@ impl Plugin for TypeScriptPgPlugin {
         self.name
     }
 
-    fn run(&self, _data: &data::Data) -> PluginResult {
-        PluginResult::from(self.get_codes(), self.get_imports(), vec![])
+    fn run(&self, _data: &data::Data) -> Result<PluginResult, error::CodegenError> {
+        Ok(PluginResult::from(
+            self.get_codes(),
+            self.get_imports(),
+            vec![],
+        ))
     }
 }
@@ -1,6 +1,64 @@
 use sqlparser::ast::ObjectName;
 
 pub fn object_name_to_string(object_name: &ObjectName) -> String {
+    // Schema qualified names, like `public.users`, end with the object name.
     let ObjectName(ident) = object_name;
-    ident[0].value.clone()
+    ident[ident.len() - 1].value.clone()
+}
+
+pub fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+pub fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+// FNV-1a hash of the content, stable across the Rust versions unlike the
+// standard library hasher.
+pub fn checksum(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+// Formats the seconds since the Unix epoch as `YYYYMMDDHHMMSS` in UTC.
+pub fn format_utc_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86400) as i64;
+    let time = seconds % 86400;
+    // Converts the days to the civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{year:04}{month:02}{day:02}{:02}{:02}{:02}",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_timestamps() {
+        assert_eq!(format_utc_timestamp(0), "19700101000000");
+        assert_eq!(format_utc_timestamp(951827696), "20000229123456");
+        assert_eq!(format_utc_timestamp(1704067199), "20231231235959");
+    }
 }